@@ -0,0 +1,161 @@
+//! Command-line tooling for Solana program interfaces
+
+use clap::{Parser, Subcommand};
+use spl_interface_instructions_codegen::{
+    adapter::{generate_adapter, AdapterMapping},
+    typescript::generate_typescript_registry,
+};
+use spl_interface_instructions_registry::{
+    embedded::read_embedded_manifests,
+    lockfile::{diff_lockfiles, parse_lockfile},
+    manifest::{diff_manifests, InterfaceManifest, ManifestInstructionStatus},
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Parser)]
+#[command(
+    name = "spl-interface",
+    about = "Tooling for Solana program interfaces"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate TypeScript clients for every interface in the registry
+    Typescript {
+        /// Directory to write the generated modules to
+        #[arg(long, short, default_value = "generated")]
+        out_dir: PathBuf,
+    },
+    /// Generate a native program exposing a legacy program through
+    /// an interface, from a mapping file
+    Adapter {
+        /// The JSON file mapping interface instructions to the legacy
+        /// program's instructions
+        mapping: PathBuf,
+        /// Directory to write the adapter crate to
+        #[arg(long, short, default_value = "adapter")]
+        out_dir: PathBuf,
+    },
+    /// Print the interface manifest embedded in a program binary
+    Manifest {
+        /// The program's `.so` file
+        program: PathBuf,
+    },
+    /// Compare the interfaces of a program's current build and its
+    /// upgrade, failing on breaking changes
+    ///
+    /// Takes two program binaries (or embedded manifests), or two
+    /// `interfaces.lock` files to also compare custom instructions and
+    /// argument layouts
+    Diff {
+        /// The current build
+        old: PathBuf,
+        /// The upgrade
+        new: PathBuf,
+    },
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    match Cli::parse().command {
+        Command::Typescript { out_dir } => {
+            fs::create_dir_all(&out_dir)?;
+            for file in generate_typescript_registry() {
+                let path = out_dir.join(&file.file_name);
+                fs::write(&path, file.contents)?;
+                println!("Wrote {}", path.display());
+            }
+        }
+        Command::Adapter { mapping, out_dir } => {
+            let mapping = AdapterMapping::from_json(&fs::read_to_string(mapping)?)?;
+            for file in generate_adapter(&mapping)? {
+                let path = out_dir.join(&file.file_name);
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&path, file.contents)?;
+                println!("Wrote {}", path.display());
+            }
+        }
+        Command::Manifest { program } => {
+            for manifest in read_embedded_manifests(&fs::read(program)?)? {
+                for interface in &manifest.interfaces {
+                    println!("{}", interface.qualified_namespace());
+                    for instruction in &interface.instructions {
+                        let status = match instruction.status {
+                            ManifestInstructionStatus::Implemented => "implemented",
+                            ManifestInstructionStatus::Omitted => "omitted",
+                            ManifestInstructionStatus::Unsupported => "unsupported",
+                        };
+                        let discriminator: String = instruction
+                            .discriminator
+                            .iter()
+                            .map(|b| format!("{:02x}", b))
+                            .collect();
+                        println!(
+                            "  {:<24} {} {}{}",
+                            instruction.namespace,
+                            discriminator,
+                            status,
+                            if instruction.optional {
+                                " (optional)"
+                            } else {
+                                ""
+                            }
+                        );
+                    }
+                }
+            }
+        }
+        Command::Diff { old, new } => {
+            // (description, breaking)
+            let changes: Vec<(String, bool)> = match (is_lockfile(&old), is_lockfile(&new)) {
+                (true, true) => diff_lockfiles(
+                    &parse_lockfile(&fs::read_to_string(old)?)?,
+                    &parse_lockfile(&fs::read_to_string(new)?)?,
+                )
+                .iter()
+                .map(|change| (change.to_string(), change.is_breaking()))
+                .collect(),
+                (false, false) => diff_manifests(&read_manifest(&old)?, &read_manifest(&new)?)
+                    .iter()
+                    .map(|change| (change.to_string(), change.is_breaking()))
+                    .collect(),
+                _ => return Err("Compare two lockfiles or two program binaries".into()),
+            };
+            for (description, breaking) in &changes {
+                let kind = if *breaking { "breaking" } else { "compatible" };
+                println!("{:<10} {}", kind, description);
+            }
+            let breaking = changes.iter().filter(|(_, breaking)| *breaking).count();
+            if breaking > 0 {
+                return Err(format!("{} breaking interface change(s)", breaking).into());
+            }
+            if changes.is_empty() {
+                println!("No interface changes");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether a path names an `interfaces.lock` file
+fn is_lockfile(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "lock")
+}
+
+/// Reads every manifest embedded in a program binary as one manifest
+fn read_manifest(path: &Path) -> Result<InterfaceManifest, Box<dyn std::error::Error>> {
+    let mut merged = InterfaceManifest::default();
+    for manifest in read_embedded_manifests(&fs::read(path)?)? {
+        merged.merge(manifest);
+    }
+    Ok(merged)
+}
@@ -0,0 +1,54 @@
+//! Client helpers for calling interface view instructions against a
+//! local bank (`solana-program-test`)
+
+use borsh::BorshDeserialize;
+use solana_program_test::{BanksClient, BanksClientError};
+use solana_sdk::{
+    instruction::Instruction, signature::Keypair, signer::Signer, transaction::Transaction,
+    transaction::TransactionError,
+};
+use spl_interface_instructions_registry::views::decode_return_data;
+
+/// Errors that may be returned when simulating a view instruction
+#[derive(Debug, thiserror::Error)]
+pub enum ViewError {
+    /// The bank could not simulate the transaction
+    #[error("Failed to simulate view instruction: {0}")]
+    Simulation(#[from] BanksClientError),
+    /// The view instruction failed
+    #[error("View instruction failed: {0}")]
+    Transaction(#[from] TransactionError),
+    /// The program returned no data, or data that doesn't decode as
+    /// the view's return type
+    #[error("View instruction returned no data of the expected type")]
+    InvalidReturnData,
+}
+
+/// Simulates a view instruction against a local bank and decodes its
+/// borsh-encoded return data as `T`
+///
+/// Nothing is committed to the bank, so `payer` only needs to be
+/// funded, not to pay
+pub async fn simulate_view<T: BorshDeserialize>(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    instruction: Instruction,
+) -> Result<T, ViewError> {
+    let program_id = instruction.program_id;
+    let recent_blockhash = banks_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    let simulation = banks_client.simulate_transaction(transaction).await?;
+    if let Some(Err(e)) = simulation.result {
+        return Err(e.into());
+    }
+    let return_data = simulation
+        .simulation_details
+        .and_then(|details| details.return_data)
+        .map(|return_data| (return_data.program_id, return_data.data));
+    decode_return_data(&program_id, return_data).ok_or(ViewError::InvalidReturnData)
+}
@@ -0,0 +1,373 @@
+//! Adapter program generator
+//!
+//! Legacy programs such as SPL Token predate interface discriminators
+//! (SPL Token's `transfer` is tag `3`). Given a mapping file, this
+//! module emits a small native program that accepts interface
+//! instructions and forwards each one to the legacy program through a
+//! CPI, so that existing deployments can take part in interface-based
+//! routing.
+//!
+//! A mapping file is JSON:
+//!
+//! ```json
+//! {
+//!   "interface": "srfc20_token",
+//!   "legacy_program_id": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+//!   "instructions": {
+//!     "transfer": { "data": [3], "accounts": ["from", "recipient", "authority"] }
+//!   },
+//!   "unsupported": ["mint_to"]
+//! }
+//! ```
+//!
+//! Each instruction's `data` is written ahead of the interface
+//! arguments, which are forwarded verbatim, and `accounts` lists the
+//! interface accounts in the order the legacy program expects them.
+//! The adapter expects the interface accounts in interface order,
+//! followed by the legacy program.
+//!
+//! Every required interface instruction must be mapped, or listed
+//! under `"unsupported"` if the legacy program has no counterpart, in
+//! which case the adapter rejects it with
+//! `InterfaceProgramError::UnsupportedInterfaceInstruction`.
+
+use serde::Deserialize;
+use spl_interface_instructions_registry::{
+    error::InterfaceProgramError, registered_interface, RegisteredInterface,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use crate::interface_name;
+
+/// Header written at the top of every generated file
+const HEADER: &str = "// This file was generated by spl-interface-instructions-codegen.\n\
+                      // Do not edit it by hand.\n";
+
+/// A mapping from an interface to a legacy program's instructions
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct AdapterMapping {
+    /// The qualified namespace of the interface the adapter exposes
+    /// (ie. `srfc20_token` or `srfc20_token@2`)
+    pub interface: String,
+    /// The base58 address of the legacy program
+    pub legacy_program_id: String,
+    /// The name of the generated crate, defaulting to
+    /// `<interface>_adapter`
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The legacy instruction each interface instruction maps to,
+    /// keyed by instruction namespace
+    pub instructions: BTreeMap<String, LegacyInstruction>,
+    /// Interface instructions the legacy program has no counterpart for
+    #[serde(default)]
+    pub unsupported: Vec<String>,
+}
+
+/// How an interface instruction is expressed for the legacy program
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct LegacyInstruction {
+    /// Instruction data written ahead of the forwarded arguments
+    pub data: Vec<u8>,
+    /// Interface account names, in the order the legacy program
+    /// expects them
+    pub accounts: Vec<String>,
+}
+
+/// Errors raised while generating an adapter program
+#[derive(Debug)]
+pub enum AdapterError {
+    /// The mapping file is not valid JSON for an `AdapterMapping`
+    InvalidMapping(serde_json::Error),
+    /// The mapped interface is not in the registry
+    UnknownInterface(String),
+    /// The mapped instruction is not part of the interface
+    UnknownInstruction(String),
+    /// A mapped account is not one of the instruction's accounts
+    UnknownAccount {
+        instruction: String,
+        account: String,
+    },
+    /// Required interface instructions are neither mapped nor listed as
+    /// unsupported
+    UnmappedInstructions(Vec<String>),
+    /// An interface instruction is listed more than once, either both
+    /// mapped and unsupported or twice as unsupported
+    DuplicateInstruction(String),
+}
+
+impl fmt::Display for AdapterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMapping(e) => write!(f, "Invalid adapter mapping: {}", e),
+            Self::UnknownInterface(interface) => write!(f, "Unknown interface `{}`", interface),
+            Self::UnknownInstruction(instruction) => {
+                write!(f, "Unknown interface instruction `{}`", instruction)
+            }
+            Self::UnknownAccount {
+                instruction,
+                account,
+            } => write!(
+                f,
+                "Unknown account `{}` for interface instruction `{}`",
+                account, instruction
+            ),
+            Self::UnmappedInstructions(instructions) => write!(
+                f,
+                "Required interface instructions are neither mapped nor listed as unsupported: {}",
+                instructions.join(", ")
+            ),
+            Self::DuplicateInstruction(instruction) => write!(
+                f,
+                "Interface instruction `{}` is listed more than once",
+                instruction
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AdapterError {}
+
+/// A generated adapter program source file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdapterFile {
+    /// The file name, relative to the adapter crate's root
+    pub file_name: String,
+    /// The file contents
+    pub contents: String,
+}
+
+impl AdapterMapping {
+    /// Parses a mapping file
+    pub fn from_json(json: &str) -> Result<Self, AdapterError> {
+        serde_json::from_str(json).map_err(AdapterError::InvalidMapping)
+    }
+
+    /// The name of the generated crate
+    pub fn crate_name(&self, interface: &RegisteredInterface) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("{}_adapter", interface_name(interface)))
+    }
+}
+
+/// Generates the adapter crate (`Cargo.toml` and `src/lib.rs`) for a
+/// mapping
+///
+/// Fails if a required interface instruction is neither mapped nor
+/// listed as unsupported, so that an adapter can't claim an interface
+/// it only partly implements, or if an instruction is listed more than
+/// once, since only its first routing arm could ever match
+pub fn generate_adapter(mapping: &AdapterMapping) -> Result<Vec<AdapterFile>, AdapterError> {
+    let interface = registered_interface(&mapping.interface)
+        .ok_or_else(|| AdapterError::UnknownInterface(mapping.interface.clone()))?;
+    let mut listed: BTreeSet<&String> = mapping.instructions.keys().collect();
+    if let Some(duplicate) = mapping
+        .unsupported
+        .iter()
+        .find(|namespace| !listed.insert(namespace))
+    {
+        return Err(AdapterError::DuplicateInstruction(duplicate.clone()));
+    }
+    let instructions = (interface.instructions)();
+    let unmapped: Vec<String> = instructions
+        .iter()
+        .filter(|ix| !ix.optional)
+        .map(|ix| ix.instruction_namespace.clone())
+        .filter(|namespace| {
+            !mapping.instructions.contains_key(namespace)
+                && !mapping.unsupported.contains(namespace)
+        })
+        .collect();
+    if !unmapped.is_empty() {
+        return Err(AdapterError::UnmappedInstructions(unmapped));
+    }
+
+    let mut arms = String::new();
+    for (instruction_namespace, legacy) in &mapping.instructions {
+        let instruction = instructions
+            .iter()
+            .find(|ix| &ix.instruction_namespace == instruction_namespace)
+            .ok_or_else(|| AdapterError::UnknownInstruction(instruction_namespace.clone()))?;
+        let interface_accounts = (interface.instruction_accounts)(instruction_namespace);
+        let order = legacy
+            .accounts
+            .iter()
+            .map(|account| {
+                interface_accounts
+                    .iter()
+                    .position(|a| a.name == account)
+                    .ok_or_else(|| AdapterError::UnknownAccount {
+                        instruction: instruction_namespace.clone(),
+                        account: account.clone(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        arms.push_str(&format!(
+            "        // {}::{}\n        {} => forward(accounts, {}, &{}, args, &{}),\n",
+            mapping.interface,
+            instruction_namespace,
+            byte_array(&instruction.discriminator()),
+            interface_accounts.len(),
+            byte_array(&legacy.data),
+            index_array(&order),
+        ));
+    }
+    for instruction_namespace in &mapping.unsupported {
+        let instruction = instructions
+            .iter()
+            .find(|ix| &ix.instruction_namespace == instruction_namespace)
+            .ok_or_else(|| AdapterError::UnknownInstruction(instruction_namespace.clone()))?;
+        arms.push_str(&format!(
+            "        // {}::{} (unsupported)\n        {} => Err(ProgramError::Custom({:#X})),\n",
+            mapping.interface,
+            instruction_namespace,
+            byte_array(&instruction.discriminator()),
+            InterfaceProgramError::UnsupportedInterfaceInstruction as u32,
+        ));
+    }
+
+    Ok(vec![
+        AdapterFile {
+            file_name: "Cargo.toml".to_string(),
+            contents: cargo_toml(&mapping.crate_name(&interface)),
+        },
+        AdapterFile {
+            file_name: "src/lib.rs".to_string(),
+            contents: lib_rs(mapping, &arms),
+        },
+    ])
+}
+
+/// Generates the adapter crate's manifest
+fn cargo_toml(name: &str) -> String {
+    format!(
+        "[package]\n\
+         name = \"{name}\"\n\
+         version = \"0.1.0\"\n\
+         edition = \"2021\"\n\n\
+         [lib]\n\
+         crate-type = [\"cdylib\", \"lib\"]\n\n\
+         [features]\n\
+         no-entrypoint = []\n\n\
+         [dependencies]\n\
+         solana-program = \">=1.14, <1.17\"\n\n\
+         [dev-dependencies]\n\
+         solana-program-test = \">=1.14, <1.17\"\n\
+         solana-sdk = \">=1.14, <1.17\"\n"
+    )
+}
+
+/// Generates the adapter program
+fn lib_rs(mapping: &AdapterMapping, arms: &str) -> String {
+    let mut src = HEADER.to_string();
+    src.push_str(&format!(
+        "//! Adapter exposing `{}` through the `{}` interface\n\n",
+        mapping.legacy_program_id, mapping.interface
+    ));
+    src.push_str(
+        "use solana_program::{\n    \
+         account_info::AccountInfo,\n    \
+         entrypoint::ProgramResult,\n    \
+         instruction::{AccountMeta, Instruction},\n    \
+         program::invoke,\n    \
+         program_error::ProgramError,\n    \
+         pubkey::Pubkey,\n\
+         };\n\n",
+    );
+    src.push_str(&format!(
+        "/// The legacy program interface instructions are forwarded to\n\
+         pub const LEGACY_PROGRAM_ID: Pubkey =\n    \
+         solana_program::pubkey!(\"{}\");\n\n",
+        mapping.legacy_program_id
+    ));
+    src.push_str(
+        "#[cfg(not(feature = \"no-entrypoint\"))]\n\
+         solana_program::entrypoint!(process_instruction);\n\n",
+    );
+    src.push_str(
+        "/// Routes an interface instruction to its legacy counterpart\n\
+         pub fn process_instruction(\n    \
+         _program_id: &Pubkey,\n    \
+         accounts: &[AccountInfo],\n    \
+         data: &[u8],\n\
+         ) -> ProgramResult {\n    \
+         if data.len() < 8 {\n        \
+         return Err(ProgramError::InvalidInstructionData);\n    \
+         }\n    \
+         let (discriminator, args) = data.split_at(8);\n    \
+         match discriminator {\n",
+    );
+    src.push_str(arms);
+    src.push_str(
+        "        _ => Err(ProgramError::InvalidInstructionData),\n    \
+         }\n\
+         }\n\n",
+    );
+    src.push_str(
+        "/// Forwards an instruction to the legacy program, which follows the\n\
+         /// `interface_accounts` interface accounts, reordered by `order`\n\
+         fn forward(\n    \
+         accounts: &[AccountInfo],\n    \
+         interface_accounts: usize,\n    \
+         legacy_data: &[u8],\n    \
+         args: &[u8],\n    \
+         order: &[usize],\n\
+         ) -> ProgramResult {\n    \
+         let legacy_program = accounts\n        \
+         .get(interface_accounts)\n        \
+         .ok_or(ProgramError::NotEnoughAccountKeys)?;\n    \
+         if legacy_program.key != &LEGACY_PROGRAM_ID {\n        \
+         return Err(ProgramError::IncorrectProgramId);\n    \
+         }\n    \
+         let mut account_infos: Vec<AccountInfo> = \
+         order.iter().map(|&i| accounts[i].clone()).collect();\n    \
+         let metas = account_infos\n        \
+         .iter()\n        \
+         .map(|a| AccountMeta {\n            \
+         pubkey: *a.key,\n            \
+         is_signer: a.is_signer,\n            \
+         is_writable: a.is_writable,\n        \
+         })\n        \
+         .collect();\n    \
+         let mut data = legacy_data.to_vec();\n    \
+         data.extend_from_slice(args);\n    \
+         account_infos.push(legacy_program.clone());\n    \
+         invoke(\n        \
+         &Instruction {\n            \
+         program_id: LEGACY_PROGRAM_ID,\n            \
+         accounts: metas,\n            \
+         data,\n        \
+         },\n        \
+         &account_infos,\n    \
+         )\n\
+         }\n",
+    );
+    src
+}
+
+/// Formats bytes as a Rust array literal
+fn byte_array(bytes: &[u8]) -> String {
+    format!(
+        "[{}]",
+        bytes
+            .iter()
+            .map(|b| b.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Formats account indices as a Rust array literal
+fn index_array(indices: &[usize]) -> String {
+    format!(
+        "[{}]",
+        indices
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
@@ -0,0 +1,159 @@
+//! Exporter for Codama (formerly Kinobi) node trees
+//!
+//! Each interface becomes a `rootNode` wrapping a `programNode`,
+//! with one `instructionNode` per interface instruction. The 8-byte
+//! interface discriminator is emitted as a constant leading
+//! argument along with a `fieldDiscriminatorNode` pointing at it.
+
+use serde_json::{json, Value};
+use spl_interface_instructions_registry::{
+    registered_interfaces, to_camel_case, Interface, InterfaceInstruction,
+    InterfaceInstructionAccount, RegisteredInterface, RequiredArgType,
+};
+
+use crate::interface_name;
+
+/// The Codama standard version the generated trees conform to
+pub const CODAMA_VERSION: &str = "1.0.0";
+
+/// Builds the Codama `rootNode` for an `Interface`
+pub fn codama_root_node<I: Interface>() -> Value {
+    root_node(&RegisteredInterface::of::<I>())
+}
+
+/// Builds a Codama `rootNode` for every interface in the registry
+pub fn codama_registry() -> Vec<Value> {
+    registered_interfaces().iter().map(root_node).collect()
+}
+
+/// Builds the Codama `rootNode` for a registered interface
+pub fn root_node(interface: &RegisteredInterface) -> Value {
+    json!({
+        "kind": "rootNode",
+        "standard": "codama",
+        "version": CODAMA_VERSION,
+        "program": program_node(interface),
+        "additionalPrograms": [],
+    })
+}
+
+/// Builds the Codama `programNode` for a registered interface
+///
+/// Interfaces are not bound to a single program, so the node's
+/// `publicKey` is left empty for the client to supply
+pub fn program_node(interface: &RegisteredInterface) -> Value {
+    let instructions: Vec<Value> = (interface.instructions)()
+        .iter()
+        .map(|ix| {
+            instruction_node(
+                ix,
+                &(interface.instruction_accounts)(&ix.instruction_namespace),
+            )
+        })
+        .collect();
+    json!({
+        "kind": "programNode",
+        "name": to_camel_case(&interface_name(interface)),
+        "publicKey": "",
+        "version": format!("{}.0.0", interface.version),
+        "docs": [format!("The `{}` interface", interface.qualified_namespace())],
+        "accounts": [],
+        "instructions": instructions,
+        "definedTypes": [],
+        "pdas": [],
+        "errors": [],
+    })
+}
+
+/// Builds the Codama `instructionNode` for an interface instruction
+pub fn instruction_node(
+    instruction: &InterfaceInstruction,
+    accounts: &[InterfaceInstructionAccount],
+) -> Value {
+    let mut arguments = vec![discriminator_argument_node(instruction)];
+    for (name, arg_type) in &instruction.required_args {
+        arguments.push(json!({
+            "kind": "instructionArgumentNode",
+            "name": to_camel_case(name),
+            "docs": [],
+            "type": type_node(arg_type),
+        }));
+    }
+    let accounts: Vec<Value> = accounts
+        .iter()
+        .map(|account| {
+            json!({
+                "kind": "instructionAccountNode",
+                "name": to_camel_case(account.name),
+                "isWritable": account.is_writable,
+                "isSigner": account.is_signer,
+                "isOptional": false,
+                "docs": [],
+            })
+        })
+        .collect();
+    json!({
+        "kind": "instructionNode",
+        "name": to_camel_case(&instruction.instruction_namespace),
+        "docs": [format!(
+            "`{}::{}`",
+            instruction.qualified_interface_namespace(),
+            instruction.instruction_namespace
+        )],
+        "optionalAccountStrategy": "programId",
+        "accounts": accounts,
+        "arguments": arguments,
+        "discriminators": [{
+            "kind": "fieldDiscriminatorNode",
+            "name": "discriminator",
+            "offset": 0,
+        }],
+    })
+}
+
+/// Builds the constant 8-byte discriminator argument, which
+/// clients fill in automatically
+fn discriminator_argument_node(instruction: &InterfaceInstruction) -> Value {
+    let data: String = instruction
+        .discriminator()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    json!({
+        "kind": "instructionArgumentNode",
+        "name": "discriminator",
+        "docs": [],
+        "type": {
+            "kind": "fixedSizeTypeNode",
+            "size": 8,
+            "type": { "kind": "bytesTypeNode" },
+        },
+        "defaultValue": {
+            "kind": "bytesValueNode",
+            "data": data,
+            "encoding": "base16",
+        },
+        "defaultValueStrategy": "omitted",
+    })
+}
+
+/// Maps a `RequiredArgType` to its borsh-compatible Codama type node
+pub fn type_node(arg_type: &RequiredArgType) -> Value {
+    match arg_type {
+        RequiredArgType::U8 => number_type_node("u8"),
+        RequiredArgType::U16 => number_type_node("u16"),
+        RequiredArgType::U32 => number_type_node("u32"),
+        RequiredArgType::U64 => number_type_node("u64"),
+        RequiredArgType::U128 => number_type_node("u128"),
+        RequiredArgType::String => json!({
+            "kind": "sizePrefixTypeNode",
+            "type": { "kind": "stringTypeNode", "encoding": "utf8" },
+            "prefix": number_type_node("u32"),
+        }),
+        RequiredArgType::Pubkey => json!({ "kind": "publicKeyTypeNode" }),
+    }
+}
+
+fn number_type_node(format: &str) -> Value {
+    json!({ "kind": "numberTypeNode", "format": format, "endian": "le" })
+}
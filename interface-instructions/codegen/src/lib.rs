@@ -0,0 +1,15 @@
+//! Client code generators for the interfaces defined in the
+//! registry, so that one canonical sRFC definition can feed every
+//! client toolchain
+
+pub mod adapter;
+pub mod codama;
+pub mod typescript;
+
+use spl_interface_instructions_registry::RegisteredInterface;
+
+/// Returns the `snake_case` name used for an interface's generated
+/// items, suffixing any version past the first (ie. `srfc20_token_v2`)
+pub(crate) fn interface_name(interface: &RegisteredInterface) -> String {
+    interface.qualified_namespace().replace('@', "_v")
+}
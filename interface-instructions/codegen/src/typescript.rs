@@ -0,0 +1,217 @@
+//! TypeScript client generator
+//!
+//! Emits one module per interface containing the discriminator
+//! constants, `@coral-xyz/borsh` argument layouts and
+//! `@solana/web3.js` `TransactionInstruction` builders for every
+//! interface instruction.
+
+use spl_interface_instructions_registry::{
+    registered_interfaces, to_camel_case, to_pascal_case, Interface, InterfaceInstruction,
+    InterfaceInstructionAccount, RegisteredInterface, RequiredArgType,
+};
+
+use crate::interface_name;
+
+/// Header written at the top of every generated file
+const HEADER: &str = "// This file was generated by spl-interface-instructions-codegen.\n\
+                      // Do not edit it by hand.\n";
+
+/// A generated TypeScript source file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeScriptFile {
+    /// The file name, relative to the output directory
+    pub file_name: String,
+    /// The file contents
+    pub contents: String,
+}
+
+/// Generates the TypeScript module for an `Interface`
+pub fn generate_typescript<I: Interface>() -> TypeScriptFile {
+    interface_module(&RegisteredInterface::of::<I>())
+}
+
+/// Generates a TypeScript module for every interface in the
+/// registry, plus an `index.ts` re-exporting each of them
+pub fn generate_typescript_registry() -> Vec<TypeScriptFile> {
+    let interfaces = registered_interfaces();
+    let mut files: Vec<TypeScriptFile> = interfaces.iter().map(interface_module).collect();
+    let mut index = HEADER.to_string();
+    for interface in &interfaces {
+        let module = to_camel_case(&interface_name(interface));
+        index.push_str(&format!("export * as {module} from \"./{module}\";\n"));
+    }
+    files.push(TypeScriptFile {
+        file_name: "index.ts".to_string(),
+        contents: index,
+    });
+    files
+}
+
+/// Generates the TypeScript module for a registered interface
+pub fn interface_module(interface: &RegisteredInterface) -> TypeScriptFile {
+    let mut contents = HEADER.to_string();
+    contents.push_str("import * as borsh from \"@coral-xyz/borsh\";\n");
+    contents.push_str("import { PublicKey, TransactionInstruction } from \"@solana/web3.js\";\n");
+    contents.push_str("import BN from \"bn.js\";\n\n");
+    contents.push_str(&format!(
+        "export const NAMESPACE = \"{}\";\n",
+        interface.namespace
+    ));
+    contents.push_str(&format!("export const VERSION = {};\n", interface.version));
+    for instruction in (interface.instructions)() {
+        let accounts = (interface.instruction_accounts)(&instruction.instruction_namespace);
+        contents.push('\n');
+        contents.push_str(&instruction_source(&instruction, &accounts));
+    }
+    TypeScriptFile {
+        file_name: format!("{}.ts", to_camel_case(&interface_name(interface))),
+        contents,
+    }
+}
+
+/// Generates the discriminator constant, layout and builder for a
+/// single interface instruction
+fn instruction_source(
+    instruction: &InterfaceInstruction,
+    accounts: &[InterfaceInstructionAccount],
+) -> String {
+    let name = to_pascal_case(&instruction.instruction_namespace);
+    let discriminator_const = format!(
+        "{}_DISCRIMINATOR",
+        instruction.instruction_namespace.to_uppercase()
+    );
+    let discriminator_bytes = instruction
+        .discriminator()
+        .iter()
+        .map(|b| b.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let has_args = !instruction.required_args.is_empty();
+
+    let mut src = format!(
+        "/** `sha256(\"{}\")[..8]` */\n\
+         export const {discriminator_const} = Buffer.from([{discriminator_bytes}]);\n\n",
+        instruction.discriminator_preimage()
+    );
+
+    // Args & layout
+    if has_args {
+        src.push_str(&format!("export interface {name}Args {{\n"));
+        for (arg_name, arg_type) in &instruction.required_args {
+            src.push_str(&format!(
+                "  {}: {};\n",
+                to_camel_case(arg_name),
+                ts_type(arg_type)
+            ));
+        }
+        src.push_str("}\n\n");
+        src.push_str(&format!(
+            "export const {name}ArgsLayout = borsh.struct<{name}Args>([\n"
+        ));
+        for (arg_name, arg_type) in &instruction.required_args {
+            src.push_str(&format!(
+                "  borsh.{}(\"{}\"),\n",
+                borsh_layout(arg_type),
+                to_camel_case(arg_name)
+            ));
+        }
+        src.push_str("]);\n\n");
+    }
+
+    // Accounts
+    src.push_str(&format!("export interface {name}Accounts {{\n"));
+    for account in accounts {
+        src.push_str(&format!("  {}: PublicKey;\n", to_camel_case(account.name)));
+    }
+    src.push_str("}\n\n");
+
+    // Builder
+    let args_param = if has_args {
+        format!(",\n  args: {name}Args")
+    } else {
+        String::new()
+    };
+    src.push_str(&format!(
+        "export function create{name}Instruction(\n  programId: PublicKey,\n  accounts: {name}Accounts{args_param}\n): TransactionInstruction {{\n"
+    ));
+    if has_args {
+        src.push_str(&format!(
+            "  const buffer = Buffer.alloc({});\n",
+            encoded_len(&instruction.required_args)
+        ));
+        src.push_str(&format!(
+            "  const len = {name}ArgsLayout.encode(args, buffer);\n"
+        ));
+        src.push_str(&format!(
+            "  const data = Buffer.concat([{discriminator_const}, buffer.subarray(0, len)]);\n"
+        ));
+    } else {
+        src.push_str(&format!(
+            "  const data = Buffer.from({discriminator_const});\n"
+        ));
+    }
+    src.push_str("  const keys = [\n");
+    for account in accounts {
+        src.push_str(&format!(
+            "    {{ pubkey: accounts.{}, isSigner: {}, isWritable: {} }},\n",
+            to_camel_case(account.name),
+            account.is_signer,
+            account.is_writable
+        ));
+    }
+    src.push_str("  ];\n");
+    src.push_str("  return new TransactionInstruction({ keys, programId, data });\n}\n");
+    src
+}
+
+/// Returns the expression computing the Borsh-encoded length of an
+/// instruction's args, so that variable-length args (`String`) are
+/// sized from their values rather than a fixed buffer
+fn encoded_len(required_args: &[(String, RequiredArgType)]) -> String {
+    let mut fixed = 0;
+    let mut variable = vec![];
+    for (arg_name, arg_type) in required_args {
+        match arg_type {
+            RequiredArgType::U8 => fixed += 1,
+            RequiredArgType::U16 => fixed += 2,
+            RequiredArgType::U32 => fixed += 4,
+            RequiredArgType::U64 => fixed += 8,
+            RequiredArgType::U128 => fixed += 16,
+            RequiredArgType::Pubkey => fixed += 32,
+            RequiredArgType::String => {
+                fixed += 4;
+                variable.push(format!(
+                    "Buffer.byteLength(args.{}, \"utf8\")",
+                    to_camel_case(arg_name)
+                ));
+            }
+        }
+    }
+    std::iter::once(fixed.to_string())
+        .chain(variable)
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// Maps a `RequiredArgType` to its TypeScript type
+fn ts_type(arg_type: &RequiredArgType) -> &'static str {
+    match arg_type {
+        RequiredArgType::U8 | RequiredArgType::U16 | RequiredArgType::U32 => "number",
+        RequiredArgType::U64 | RequiredArgType::U128 => "BN",
+        RequiredArgType::String => "string",
+        RequiredArgType::Pubkey => "PublicKey",
+    }
+}
+
+/// Maps a `RequiredArgType` to its `@coral-xyz/borsh` layout
+fn borsh_layout(arg_type: &RequiredArgType) -> &'static str {
+    match arg_type {
+        RequiredArgType::U8 => "u8",
+        RequiredArgType::U16 => "u16",
+        RequiredArgType::U32 => "u32",
+        RequiredArgType::U64 => "u64",
+        RequiredArgType::U128 => "u128",
+        RequiredArgType::String => "str",
+        RequiredArgType::Pubkey => "publicKey",
+    }
+}
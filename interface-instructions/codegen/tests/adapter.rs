@@ -0,0 +1,106 @@
+use spl_interface_instructions_codegen::adapter::{generate_adapter, AdapterError, AdapterMapping};
+use spl_interface_instructions_registry::{instructions::SRFC20, Interface};
+
+const SPL_TOKEN_SRFC20: &str = include_str!("../mappings/spl_token_srfc20.json");
+
+#[test]
+fn test_adapter_routes_discriminators() {
+    let mapping = AdapterMapping::from_json(SPL_TOKEN_SRFC20).unwrap();
+    let files = generate_adapter(&mapping).unwrap();
+    let cargo_toml = files.iter().find(|f| f.file_name == "Cargo.toml").unwrap();
+    assert!(cargo_toml
+        .contents
+        .contains("name = \"spl_token_srfc20_adapter\""));
+    let lib = files.iter().find(|f| f.file_name == "src/lib.rs").unwrap();
+    let transfer = SRFC20::instructions()[1].discriminator();
+    let bytes = transfer
+        .iter()
+        .map(|b| b.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    // `transfer` takes (mint, recipient, from, authority), while SPL
+    // Token's takes (source, destination, owner)
+    assert!(lib.contents.contains(&format!(
+        "[{}] => forward(accounts, 4, &[3], args, &[2, 1, 3]),",
+        bytes
+    )));
+}
+
+#[test]
+fn test_adapter_rejects_unknown_account() {
+    let mut mapping = AdapterMapping::from_json(SPL_TOKEN_SRFC20).unwrap();
+    mapping
+        .instructions
+        .get_mut("transfer")
+        .unwrap()
+        .accounts
+        .push("owner".to_string());
+    assert!(matches!(
+        generate_adapter(&mapping),
+        Err(AdapterError::UnknownAccount { account, .. }) if account == "owner"
+    ));
+}
+
+#[test]
+fn test_adapter_reports_unmapped_instructions() {
+    let mut mapping = AdapterMapping::from_json(SPL_TOKEN_SRFC20).unwrap();
+    mapping.instructions.remove("transfer");
+    assert!(matches!(
+        generate_adapter(&mapping),
+        Err(AdapterError::UnmappedInstructions(instructions)) if instructions == ["transfer"]
+    ));
+
+    mapping.unsupported.push("transfer".to_string());
+    let files = generate_adapter(&mapping).unwrap();
+    let lib = files.iter().find(|f| f.file_name == "src/lib.rs").unwrap();
+    let transfer = SRFC20::instructions()[1].discriminator();
+    let bytes = transfer
+        .iter()
+        .map(|b| b.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    assert!(lib.contents.contains(&format!(
+        "[{}] => Err(ProgramError::Custom(0xFFFFFF00)),",
+        bytes
+    )));
+}
+
+#[test]
+fn test_adapter_rejects_duplicate_instructions() {
+    let mut mapping = AdapterMapping::from_json(SPL_TOKEN_SRFC20).unwrap();
+    mapping.unsupported.push("transfer".to_string());
+    assert!(matches!(
+        generate_adapter(&mapping),
+        Err(AdapterError::DuplicateInstruction(instruction)) if instruction == "transfer"
+    ));
+
+    mapping.instructions.remove("transfer");
+    mapping.unsupported.push("transfer".to_string());
+    assert!(matches!(
+        generate_adapter(&mapping),
+        Err(AdapterError::DuplicateInstruction(instruction)) if instruction == "transfer"
+    ));
+}
+
+#[test]
+fn test_adapter_matches_checked_in_program() {
+    // `programs/spl-token-srfc20-adapter` is the generated adapter, built
+    // and run against SPL Token by its own tests
+    let mapping = AdapterMapping::from_json(SPL_TOKEN_SRFC20).unwrap();
+    let files = generate_adapter(&mapping).unwrap();
+    let checked_in = [
+        (
+            "Cargo.toml",
+            include_str!("../../../programs/spl-token-srfc20-adapter/Cargo.toml"),
+        ),
+        (
+            "src/lib.rs",
+            include_str!("../../../programs/spl-token-srfc20-adapter/src/lib.rs"),
+        ),
+    ];
+    assert_eq!(files.len(), checked_in.len());
+    for (file_name, contents) in checked_in {
+        let file = files.iter().find(|f| f.file_name == file_name).unwrap();
+        assert_eq!(file.contents, contents, "{} is out of date", file_name);
+    }
+}
@@ -0,0 +1,30 @@
+use spl_interface_instructions_codegen::codama::{codama_registry, codama_root_node};
+use spl_interface_instructions_registry::{instructions::SRFC20, registered_interfaces, Interface};
+
+#[test]
+fn test_codama_discriminators() {
+    let root = codama_root_node::<SRFC20>();
+    let instructions = root["program"]["instructions"].as_array().unwrap();
+    assert_eq!(instructions.len(), SRFC20::instructions().len());
+    for (node, ix) in instructions.iter().zip(SRFC20::instructions()) {
+        let expected: String = ix
+            .discriminator()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        assert_eq!(node["arguments"][0]["defaultValue"]["data"], expected);
+        if ix.is_view() {
+            assert_eq!(node["arguments"][1]["name"], "owner");
+            continue;
+        }
+        assert_eq!(node["arguments"][1]["name"], "amount");
+        assert_eq!(node["arguments"][1]["type"]["format"], "u64");
+    }
+}
+
+#[test]
+fn test_codama_registry() {
+    let roots = codama_registry();
+    assert_eq!(roots.len(), registered_interfaces().len());
+    assert_eq!(roots[3]["program"]["name"], "srfc23TokenMetadata");
+}
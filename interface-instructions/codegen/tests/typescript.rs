@@ -0,0 +1,61 @@
+use spl_interface_instructions_codegen::typescript::{
+    generate_typescript, generate_typescript_registry,
+};
+use spl_interface_instructions_registry::{
+    instructions::{SRFC20, SRFC23},
+    registered_interfaces, Interface,
+};
+
+#[test]
+fn test_typescript_discriminators() {
+    let file = generate_typescript::<SRFC20>();
+    assert_eq!(file.file_name, "srfc20Token.ts");
+    for ix in SRFC20::instructions() {
+        let bytes = ix
+            .discriminator()
+            .iter()
+            .map(|b| b.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        assert!(file.contents.contains(&format!("Buffer.from([{}])", bytes)));
+    }
+    assert!(file
+        .contents
+        .contains("export function createTransferInstruction("));
+    assert!(file.contents.contains("borsh.u64(\"amount\")"));
+    assert!(file.contents.contains("Buffer.alloc(8);"));
+}
+
+#[test]
+fn test_typescript_string_args_sized_from_values() {
+    let file = generate_typescript::<SRFC23>();
+    assert!(!file.contents.contains("Buffer.alloc(1000)"));
+    let alloc = "Buffer.alloc(12 + Buffer.byteLength(args.name, \"utf8\") \
+                 + Buffer.byteLength(args.symbol, \"utf8\") \
+                 + Buffer.byteLength(args.uri, \"utf8\"));";
+    assert!(file.contents.contains(alloc));
+
+    // Evaluate the generated size expression with a string arg longer
+    // than any fixed buffer, when a JavaScript runtime is available
+    let expression = &alloc["Buffer.alloc(".len()..alloc.len() - 2];
+    let script = format!(
+        "const args = {{ name: \"n\".repeat(5000), symbol: \"SYM\", uri: \"é\".repeat(10) }};\n\
+         process.stdout.write(String({expression}));"
+    );
+    if let Ok(output) = std::process::Command::new("node")
+        .args(["-e", &script])
+        .output()
+    {
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "5035");
+    }
+}
+
+#[test]
+fn test_typescript_registry() {
+    let files = generate_typescript_registry();
+    let index = files.iter().find(|f| f.file_name == "index.ts").unwrap();
+    assert!(index
+        .contents
+        .contains("export * as srfc22AssociatedToken from \"./srfc22AssociatedToken\";"));
+    assert_eq!(files.len(), registered_interfaces().len() + 1);
+}
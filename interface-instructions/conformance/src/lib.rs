@@ -0,0 +1,818 @@
+//! Behavioral conformance harness for Solana program interfaces
+//!
+//! The derive macros check that a program declares an interface's
+//! instructions with the right arguments. This harness checks that the
+//! instructions also behave as the interface specifies, by running a
+//! suite of cases per interface against any program loaded into a
+//! `solana-program-test` bank.
+//!
+//! Interfaces don't specify how mints and token accounts are created,
+//! so programs describe that with a `TokenFixture`, or with a
+//! `ProgramFixture` when they initialize accounts allocated by the
+//! client (like the sRFC 20 reference token). Every other
+//! instruction is built from the registry alone: its discriminator,
+//! its borsh-encoded arguments and its accounts in interface order.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use spl_interface_instructions_registry::{
+    events::logged_data,
+    instructions::{SRFC20, SRFC21, SRFC22, SRFC23},
+    Interface,
+};
+
+/// Describes how a program creates the state its interface
+/// instructions act on
+pub trait TokenFixture {
+    /// Returns the instructions creating and initializing `mint`,
+    /// paid for by `payer`
+    fn create_mint(
+        &self,
+        payer: &Pubkey,
+        mint: &Keypair,
+        mint_authority: &Pubkey,
+        freeze_authority: &Pubkey,
+    ) -> Vec<Instruction>;
+    /// Returns the instructions creating and initializing the token
+    /// account `account` of `mint`, held by `owner`
+    fn create_token_account(
+        &self,
+        payer: &Pubkey,
+        account: &Keypair,
+        mint: &Pubkey,
+        owner: &Pubkey,
+    ) -> Vec<Instruction>;
+    /// Returns the address of a mint's metadata account, or `None` if
+    /// the program doesn't implement sRFC 23, in which case its cases
+    /// are skipped
+    fn metadata_address(&self, _mint: &Pubkey) -> Option<Pubkey> {
+        None
+    }
+    /// Returns any accounts the program expects after an interface
+    /// instruction's accounts (ie. a payer and the system program)
+    fn extra_accounts(&self, _instruction_namespace: &str, _payer: &Pubkey) -> Vec<AccountMeta> {
+        vec![]
+    }
+}
+
+/// A `TokenFixture` for programs whose mints and token accounts are
+/// allocated by the client with `create_account`, then initialized by
+/// one of the program's instructions
+#[derive(Clone)]
+pub struct ProgramFixture {
+    program_id: Pubkey,
+    mint_len: usize,
+    token_account_len: usize,
+    initialize_mint: fn(&Pubkey, &Pubkey, &Pubkey, Option<&Pubkey>) -> Instruction,
+    initialize_account: fn(&Pubkey, &Pubkey, &Pubkey, &Pubkey) -> Instruction,
+    metadata_address: Option<fn(&Pubkey, &Pubkey) -> Pubkey>,
+    extra_accounts: Option<fn(&str, &Pubkey) -> Vec<AccountMeta>>,
+}
+impl ProgramFixture {
+    /// Creates a fixture for `program_id`, allocating `mint_len` bytes
+    /// for mints and `token_account_len` bytes for token accounts
+    ///
+    /// The initializers build the program's instructions from
+    /// `(program_id, mint, mint_authority, freeze_authority)` and
+    /// `(program_id, account, mint, owner)`
+    pub fn new(
+        program_id: Pubkey,
+        mint_len: usize,
+        token_account_len: usize,
+        initialize_mint: fn(&Pubkey, &Pubkey, &Pubkey, Option<&Pubkey>) -> Instruction,
+        initialize_account: fn(&Pubkey, &Pubkey, &Pubkey, &Pubkey) -> Instruction,
+    ) -> Self {
+        Self {
+            program_id,
+            mint_len,
+            token_account_len,
+            initialize_mint,
+            initialize_account,
+            metadata_address: None,
+            extra_accounts: None,
+        }
+    }
+    /// Derives metadata addresses from `(program_id, mint)` (sRFC 23)
+    pub fn with_metadata_address(
+        mut self,
+        metadata_address: fn(&Pubkey, &Pubkey) -> Pubkey,
+    ) -> Self {
+        self.metadata_address = Some(metadata_address);
+        self
+    }
+    /// Appends accounts to interface instructions, from
+    /// `(instruction_namespace, payer)`
+    pub fn with_extra_accounts(
+        mut self,
+        extra_accounts: fn(&str, &Pubkey) -> Vec<AccountMeta>,
+    ) -> Self {
+        self.extra_accounts = Some(extra_accounts);
+        self
+    }
+}
+impl TokenFixture for ProgramFixture {
+    fn create_mint(
+        &self,
+        payer: &Pubkey,
+        mint: &Keypair,
+        mint_authority: &Pubkey,
+        freeze_authority: &Pubkey,
+    ) -> Vec<Instruction> {
+        vec![
+            create_account(payer, &mint.pubkey(), self.mint_len, &self.program_id),
+            (self.initialize_mint)(
+                &self.program_id,
+                &mint.pubkey(),
+                mint_authority,
+                Some(freeze_authority),
+            ),
+        ]
+    }
+    fn create_token_account(
+        &self,
+        payer: &Pubkey,
+        account: &Keypair,
+        mint: &Pubkey,
+        owner: &Pubkey,
+    ) -> Vec<Instruction> {
+        vec![
+            create_account(
+                payer,
+                &account.pubkey(),
+                self.token_account_len,
+                &self.program_id,
+            ),
+            (self.initialize_account)(&self.program_id, &account.pubkey(), mint, owner),
+        ]
+    }
+    fn metadata_address(&self, mint: &Pubkey) -> Option<Pubkey> {
+        self.metadata_address
+            .map(|metadata_address| metadata_address(&self.program_id, mint))
+    }
+    fn extra_accounts(&self, instruction_namespace: &str, payer: &Pubkey) -> Vec<AccountMeta> {
+        self.extra_accounts
+            .map(|extra_accounts| extra_accounts(instruction_namespace, payer))
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the instruction creating a rent-exempt `account` of `len`
+/// bytes owned by `program_id`, paid for by `payer`
+pub fn create_account(
+    payer: &Pubkey,
+    account: &Pubkey,
+    len: usize,
+    program_id: &Pubkey,
+) -> Instruction {
+    system_instruction::create_account(
+        payer,
+        account,
+        Rent::default().minimum_balance(len),
+        len as u64,
+        program_id,
+    )
+}
+
+/// Sends a transaction paid for by the context's payer, returning its
+/// logs
+///
+/// Each transaction is sent with a new blockhash, so that repeating one
+/// keeps its signature unique
+pub async fn send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<Vec<String>, TransactionError> {
+    let recent_blockhash = context
+        .get_new_latest_blockhash()
+        .await
+        .expect("Bank is reachable");
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .expect("Bank is reachable");
+    result.result?;
+    Ok(result
+        .metadata
+        .map(|metadata| metadata.log_messages)
+        .unwrap_or_default())
+}
+
+/// The outcome of one conformance case
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CaseOutcome {
+    /// The program behaved as the interface specifies
+    Passed,
+    /// The program didn't, for the given reason
+    Failed(String),
+    /// The case couldn't run against the program, for the given reason
+    Skipped(String),
+}
+
+/// The outcome of one conformance case, along with the case
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CaseReport {
+    /// The interface the case checks
+    pub interface: &'static str,
+    /// The case's name
+    pub case: &'static str,
+    /// Whether the case passed, failed or was skipped
+    pub outcome: CaseOutcome,
+}
+
+/// Panics with every failed case, if any
+pub fn assert_conformance(reports: &[CaseReport]) {
+    let failures: Vec<String> = reports
+        .iter()
+        .filter_map(|report| match &report.outcome {
+            CaseOutcome::Failed(reason) => Some(format!(
+                "  - {}::{}: {}",
+                report.interface, report.case, reason
+            )),
+            _ => None,
+        })
+        .collect();
+    if !failures.is_empty() {
+        panic!(
+            "\n\nThe program failed the following conformance cases:\n\n{}\n",
+            failures.join("\n")
+        );
+    }
+}
+
+type CaseResult = Result<(), String>;
+
+/// A mint along with its token accounts, created for one case
+struct Token {
+    mint: Pubkey,
+    mint_authority: Keypair,
+    freeze_authority: Keypair,
+    /// The owner and address of each token account
+    accounts: Vec<(Keypair, Pubkey)>,
+}
+
+/// The sRFC 20 `token_account` layout, following its discriminator
+#[derive(BorshDeserialize)]
+struct TokenAccountPrefix {
+    mint: Pubkey,
+    owner: Pubkey,
+    amount: u64,
+}
+
+/// The sRFC 20 `transfer` event, following its discriminator
+#[derive(BorshDeserialize, Debug, PartialEq)]
+struct TransferEventPrefix {
+    from: Pubkey,
+    to: Pubkey,
+    amount: u64,
+}
+
+/// Runs the conformance cases of each interface against a program
+pub struct Conformance<'a, F: TokenFixture> {
+    context: ProgramTestContext,
+    program_id: Pubkey,
+    fixture: &'a F,
+}
+
+impl<'a, F: TokenFixture> Conformance<'a, F> {
+    /// Starts a bank from `program_test`, which must load the program
+    /// under `program_id`
+    pub async fn start(program_test: ProgramTest, program_id: Pubkey, fixture: &'a F) -> Self {
+        Self {
+            context: program_test.start_with_context().await,
+            program_id,
+            fixture,
+        }
+    }
+
+    /// Checks the sRFC 20 Token Interface: `mint_to` and `transfer`
+    /// move exactly `amount`, require the authority's signature, and
+    /// `transfer` fails without moving funds when the balance is too low
+    /// and emits `transfer`
+    pub async fn check_srfc20(&mut self) -> Vec<CaseReport> {
+        vec![
+            report::<SRFC20>(
+                "mint_to_credits_amount",
+                self.mint_to_credits_amount().await,
+            ),
+            report::<SRFC20>(
+                "mint_to_requires_authority_signature",
+                self.mint_to_requires_authority_signature().await,
+            ),
+            report::<SRFC20>(
+                "transfer_moves_exact_amount",
+                self.transfer_moves_exact_amount().await,
+            ),
+            report::<SRFC20>(
+                "transfer_requires_authority_signature",
+                self.transfer_requires_authority_signature().await,
+            ),
+            report::<SRFC20>(
+                "transfer_rejects_insufficient_funds",
+                self.transfer_rejects_insufficient_funds().await,
+            ),
+            report::<SRFC20>("transfer_emits_event", self.transfer_emits_event().await),
+        ]
+    }
+
+    /// Checks the sRFC 21 Token Interface: `burn` debits exactly
+    /// `amount` and requires the owner's signature
+    pub async fn check_srfc21(&mut self) -> Vec<CaseReport> {
+        vec![
+            report::<SRFC21>("burn_debits_amount", self.burn_debits_amount().await),
+            report::<SRFC21>(
+                "burn_requires_authority_signature",
+                self.burn_requires_authority_signature().await,
+            ),
+        ]
+    }
+
+    /// Checks the sRFC 22 Associated Token Interface: `freeze` blocks
+    /// transfers until `thaw`, and both require the freeze authority
+    pub async fn check_srfc22(&mut self) -> Vec<CaseReport> {
+        vec![
+            report::<SRFC22>(
+                "freeze_blocks_transfers",
+                self.freeze_blocks_transfers().await,
+            ),
+            report::<SRFC22>(
+                "thaw_restores_transfers",
+                self.thaw_restores_transfers().await,
+            ),
+            report::<SRFC22>(
+                "freeze_requires_authority_signature",
+                self.freeze_requires_authority_signature().await,
+            ),
+        ]
+    }
+
+    /// Checks the sRFC 23 Token Metadata Interface: `create_metadata`
+    /// writes the metadata to the metadata account and requires the
+    /// mint authority's signature
+    ///
+    /// The cases are skipped if the fixture has no metadata addresses
+    pub async fn check_srfc23(&mut self) -> Vec<CaseReport> {
+        // Whether a program implements sRFC 23 doesn't depend on the mint
+        if self.fixture.metadata_address(&Pubkey::default()).is_none() {
+            let reason = "the fixture has no metadata address";
+            return vec![
+                skipped::<SRFC23>("create_metadata_writes_metadata", reason),
+                skipped::<SRFC23>("create_metadata_requires_authority_signature", reason),
+            ];
+        }
+        vec![
+            report::<SRFC23>(
+                "create_metadata_writes_metadata",
+                self.create_metadata_writes_metadata().await,
+            ),
+            report::<SRFC23>(
+                "create_metadata_requires_authority_signature",
+                self.create_metadata_requires_authority_signature().await,
+            ),
+        ]
+    }
+
+    async fn mint_to_credits_amount(&mut self) -> CaseResult {
+        let token = self.token(1).await?;
+        self.mint_to(&token, 0, 40).await?;
+        self.mint_to(&token, 0, 2).await?;
+        expect_amount(self.amount(&token, 0).await?, 42)
+    }
+
+    async fn mint_to_requires_authority_signature(&mut self) -> CaseResult {
+        let token = self.token(1).await?;
+        let ix = self.interface_ix::<SRFC20>(
+            "mint_to",
+            &[
+                token.mint,
+                token.accounts[0].1,
+                token.mint_authority.pubkey(),
+            ],
+            Some("authority"),
+            &40u64,
+        );
+        expect_failure(self.send(&[ix], &[]).await)?;
+        expect_amount(self.amount(&token, 0).await?, 0)
+    }
+
+    async fn transfer_moves_exact_amount(&mut self) -> CaseResult {
+        let token = self.token(2).await?;
+        self.mint_to(&token, 0, 100).await?;
+        let ix = self.transfer_ix(&token, 0, 1, 30, None);
+        self.send(&[ix], &[&token.accounts[0].0])
+            .await
+            .map_err(|e| format!("transfer failed: {}", e))?;
+        expect_amount(self.amount(&token, 0).await?, 70)?;
+        expect_amount(self.amount(&token, 1).await?, 30)
+    }
+
+    async fn transfer_requires_authority_signature(&mut self) -> CaseResult {
+        let token = self.token(2).await?;
+        self.mint_to(&token, 0, 100).await?;
+        let ix = self.transfer_ix(&token, 0, 1, 30, Some("authority"));
+        expect_failure(self.send(&[ix], &[]).await)?;
+        // Signing as someone other than the owner fails as well
+        let impostor = Keypair::new();
+        let mut ix = self.transfer_ix(&token, 0, 1, 30, None);
+        ix.accounts[3].pubkey = impostor.pubkey();
+        expect_failure(self.send(&[ix], &[&impostor]).await)?;
+        expect_amount(self.amount(&token, 0).await?, 100)?;
+        expect_amount(self.amount(&token, 1).await?, 0)
+    }
+
+    async fn transfer_rejects_insufficient_funds(&mut self) -> CaseResult {
+        let token = self.token(2).await?;
+        self.mint_to(&token, 0, 10).await?;
+        let ix = self.transfer_ix(&token, 0, 1, 11, None);
+        expect_failure(self.send(&[ix], &[&token.accounts[0].0]).await)?;
+        expect_amount(self.amount(&token, 0).await?, 10)?;
+        expect_amount(self.amount(&token, 1).await?, 0)
+    }
+
+    async fn transfer_emits_event(&mut self) -> CaseResult {
+        let token = self.token(2).await?;
+        self.mint_to(&token, 0, 10).await?;
+        let ix = self.transfer_ix(&token, 0, 1, 4, None);
+        let logs = self
+            .send(&[ix], &[&token.accounts[0].0])
+            .await
+            .map_err(|e| format!("transfer failed: {}", e))?;
+        let discriminator = SRFC20::events()[0].discriminator();
+        let events: Vec<TransferEventPrefix> = logged_data(&logs)
+            .into_iter()
+            .filter(|(program_id, data)| {
+                program_id == &self.program_id && data.starts_with(&discriminator)
+            })
+            .filter_map(|(_, data)| TransferEventPrefix::deserialize(&mut &data[8..]).ok())
+            .collect();
+        let expected = TransferEventPrefix {
+            from: token.accounts[0].1,
+            to: token.accounts[1].1,
+            amount: 4,
+        };
+        match events.as_slice() {
+            [event] if event == &expected => Ok(()),
+            _ => Err(format!("expected one {:?}, got {:?}", expected, events)),
+        }
+    }
+
+    async fn burn_debits_amount(&mut self) -> CaseResult {
+        let token = self.token(1).await?;
+        self.mint_to(&token, 0, 10).await?;
+        let ix = self.burn_ix(&token, 3, None);
+        self.send(&[ix], &[&token.accounts[0].0])
+            .await
+            .map_err(|e| format!("burn failed: {}", e))?;
+        expect_amount(self.amount(&token, 0).await?, 7)
+    }
+
+    async fn burn_requires_authority_signature(&mut self) -> CaseResult {
+        let token = self.token(1).await?;
+        self.mint_to(&token, 0, 10).await?;
+        let ix = self.burn_ix(&token, 3, Some("authority"));
+        expect_failure(self.send(&[ix], &[]).await)?;
+        expect_amount(self.amount(&token, 0).await?, 10)
+    }
+
+    async fn freeze_blocks_transfers(&mut self) -> CaseResult {
+        let token = self.token(2).await?;
+        self.mint_to(&token, 0, 10).await?;
+        self.set_frozen(&token, "freeze").await?;
+        let ix = self.transfer_ix(&token, 0, 1, 1, None);
+        expect_failure(self.send(&[ix], &[&token.accounts[0].0]).await)?;
+        expect_amount(self.amount(&token, 0).await?, 10)
+    }
+
+    async fn thaw_restores_transfers(&mut self) -> CaseResult {
+        let token = self.token(2).await?;
+        self.mint_to(&token, 0, 10).await?;
+        self.set_frozen(&token, "freeze").await?;
+        self.set_frozen(&token, "thaw").await?;
+        let ix = self.transfer_ix(&token, 0, 1, 1, None);
+        self.send(&[ix], &[&token.accounts[0].0])
+            .await
+            .map_err(|e| format!("transfer after thaw failed: {}", e))?;
+        expect_amount(self.amount(&token, 1).await?, 1)
+    }
+
+    async fn freeze_requires_authority_signature(&mut self) -> CaseResult {
+        let token = self.token(2).await?;
+        self.mint_to(&token, 0, 10).await?;
+        let ix = self.interface_ix::<SRFC22>(
+            "freeze",
+            &[
+                token.mint,
+                token.accounts[0].1,
+                token.freeze_authority.pubkey(),
+            ],
+            Some("authority"),
+            &(),
+        );
+        expect_failure(self.send(&[ix], &[]).await)?;
+        let ix = self.transfer_ix(&token, 0, 1, 1, None);
+        self.send(&[ix], &[&token.accounts[0].0])
+            .await
+            .map_err(|e| format!("transfer after rejected freeze failed: {}", e))
+            .map(|_| ())
+    }
+
+    async fn create_metadata_writes_metadata(&mut self) -> CaseResult {
+        let token = self.token(0).await?;
+        let metadata = (
+            "Conformance".to_string(),
+            "CONF".to_string(),
+            "https://example.com/conformance.json".to_string(),
+        );
+        let ix = self.create_metadata_ix(&token, &metadata, None);
+        self.send(&[ix], &[&token.mint_authority])
+            .await
+            .map_err(|e| format!("create_metadata failed: {}", e))?;
+        // The interface doesn't define the metadata account's layout, so
+        // only look for the borsh-encoded metadata within it
+        let address = self.metadata_address(&token);
+        let data = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .expect("Bank is reachable")
+            .ok_or_else(|| format!("metadata account {} does not exist", address))?
+            .data;
+        let encoded = metadata.try_to_vec().expect("Metadata is serializable");
+        if !data.windows(encoded.len()).any(|window| window == encoded) {
+            return Err(format!(
+                "metadata account {} doesn't hold {:?}",
+                address, metadata
+            ));
+        }
+        Ok(())
+    }
+
+    async fn create_metadata_requires_authority_signature(&mut self) -> CaseResult {
+        let token = self.token(0).await?;
+        let metadata = ("A".to_string(), "B".to_string(), "C".to_string());
+        let ix = self.create_metadata_ix(&token, &metadata, Some("authority"));
+        expect_failure(self.send(&[ix], &[]).await)
+    }
+
+    /// Creates a mint with a token account for each of `owners` new
+    /// owners
+    async fn token(&mut self, owners: usize) -> Result<Token, String> {
+        let payer = self.context.payer.pubkey();
+        let mint = Keypair::new();
+        let mint_authority = Keypair::new();
+        let freeze_authority = Keypair::new();
+        let mut instructions = self.fixture.create_mint(
+            &payer,
+            &mint,
+            &mint_authority.pubkey(),
+            &freeze_authority.pubkey(),
+        );
+        let account_keypairs: Vec<Keypair> = (0..owners).map(|_| Keypair::new()).collect();
+        let mut accounts = vec![];
+        for account in &account_keypairs {
+            let owner = Keypair::new();
+            instructions.extend(self.fixture.create_token_account(
+                &payer,
+                account,
+                &mint.pubkey(),
+                &owner.pubkey(),
+            ));
+            accounts.push((owner, account.pubkey()));
+        }
+        let mut signers = vec![&mint];
+        signers.extend(account_keypairs.iter());
+        self.send(&instructions, &signers)
+            .await
+            .map_err(|e| format!("fixture setup failed: {}", e))?;
+        Ok(Token {
+            mint: mint.pubkey(),
+            mint_authority,
+            freeze_authority,
+            accounts,
+        })
+    }
+
+    async fn mint_to(&mut self, token: &Token, account: usize, amount: u64) -> CaseResult {
+        let ix = self.interface_ix::<SRFC20>(
+            "mint_to",
+            &[
+                token.mint,
+                token.accounts[account].1,
+                token.mint_authority.pubkey(),
+            ],
+            None,
+            &amount,
+        );
+        self.send(&[ix], &[&token.mint_authority])
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("mint_to failed: {}", e))
+    }
+
+    async fn set_frozen(&mut self, token: &Token, instruction_namespace: &str) -> CaseResult {
+        let ix = self.interface_ix::<SRFC22>(
+            instruction_namespace,
+            &[
+                token.mint,
+                token.accounts[0].1,
+                token.freeze_authority.pubkey(),
+            ],
+            None,
+            &(),
+        );
+        self.send(&[ix], &[&token.freeze_authority])
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("{} failed: {}", instruction_namespace, e))
+    }
+
+    fn transfer_ix(
+        &self,
+        token: &Token,
+        from: usize,
+        to: usize,
+        amount: u64,
+        unsigned: Option<&str>,
+    ) -> Instruction {
+        self.interface_ix::<SRFC20>(
+            "transfer",
+            &[
+                token.mint,
+                token.accounts[to].1,
+                token.accounts[from].1,
+                token.accounts[from].0.pubkey(),
+            ],
+            unsigned,
+            &amount,
+        )
+    }
+
+    fn burn_ix(&self, token: &Token, amount: u64, unsigned: Option<&str>) -> Instruction {
+        self.interface_ix::<SRFC21>(
+            "burn",
+            &[
+                token.mint,
+                token.accounts[0].1,
+                token.accounts[0].0.pubkey(),
+            ],
+            unsigned,
+            &amount,
+        )
+    }
+
+    fn create_metadata_ix(
+        &self,
+        token: &Token,
+        metadata: &(String, String, String),
+        unsigned: Option<&str>,
+    ) -> Instruction {
+        self.interface_ix::<SRFC23>(
+            "create_metadata",
+            &[
+                token.mint,
+                self.metadata_address(token),
+                token.mint_authority.pubkey(),
+            ],
+            unsigned,
+            metadata,
+        )
+    }
+
+    /// Builds an interface instruction from the registry, with
+    /// `accounts` in interface order and the account named `unsigned`
+    /// (if any) not marked as a signer
+    fn interface_ix<I: Interface>(
+        &self,
+        instruction_namespace: &str,
+        accounts: &[Pubkey],
+        unsigned: Option<&str>,
+        args: &impl BorshSerialize,
+    ) -> Instruction {
+        let interface_ix = I::instructions()
+            .into_iter()
+            .find(|ix| ix.instruction_namespace == instruction_namespace)
+            .expect("Conformance cases only use registered instructions");
+        let mut data = interface_ix.discriminator().to_vec();
+        args.serialize(&mut data)
+            .expect("Instruction data is always serializable");
+        let mut metas: Vec<AccountMeta> = I::instruction_accounts(instruction_namespace)
+            .into_iter()
+            .zip(accounts)
+            .map(|(account, pubkey)| AccountMeta {
+                pubkey: *pubkey,
+                is_signer: account.is_signer && unsigned != Some(account.name),
+                is_writable: account.is_writable,
+            })
+            .collect();
+        metas.extend(
+            self.fixture
+                .extra_accounts(instruction_namespace, &self.context.payer.pubkey()),
+        );
+        Instruction {
+            program_id: self.program_id,
+            accounts: metas,
+            data,
+        }
+    }
+
+    /// Sends a transaction paid for by the payer, returning its logs
+    async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Vec<String>, TransactionError> {
+        send(&mut self.context, instructions, signers).await
+    }
+
+    /// Returns the address of a mint's metadata account, once
+    /// `check_srfc23` has checked that the fixture has one
+    fn metadata_address(&self, token: &Token) -> Pubkey {
+        self.fixture
+            .metadata_address(&token.mint)
+            .expect("sRFC 23 cases only run with metadata addresses")
+    }
+
+    /// Reads a token account's balance through the sRFC 20
+    /// `token_account` layout
+    async fn amount(&mut self, token: &Token, account: usize) -> Result<u64, String> {
+        let address = token.accounts[account].1;
+        let data = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .expect("Bank is reachable")
+            .ok_or_else(|| format!("token account {} does not exist", address))?
+            .data;
+        let discriminator = SRFC20::accounts()[0].discriminator();
+        if !data.starts_with(&discriminator) {
+            return Err(format!(
+                "token account {} lacks the `token_account` discriminator",
+                address
+            ));
+        }
+        let prefix = TokenAccountPrefix::deserialize(&mut &data[8..])
+            .map_err(|_| format!("token account {} is malformed", address))?;
+        if prefix.mint != token.mint || prefix.owner != token.accounts[account].0.pubkey() {
+            return Err(format!(
+                "token account {} has the wrong mint or owner",
+                address
+            ));
+        }
+        Ok(prefix.amount)
+    }
+}
+
+fn report<I: Interface>(case: &'static str, result: CaseResult) -> CaseReport {
+    CaseReport {
+        interface: I::NAMESPACE,
+        case,
+        outcome: match result {
+            Ok(()) => CaseOutcome::Passed,
+            Err(reason) => CaseOutcome::Failed(reason),
+        },
+    }
+}
+
+fn skipped<I: Interface>(case: &'static str, reason: &str) -> CaseReport {
+    CaseReport {
+        interface: I::NAMESPACE,
+        case,
+        outcome: CaseOutcome::Skipped(reason.to_string()),
+    }
+}
+
+fn expect_amount(amount: u64, expected: u64) -> CaseResult {
+    if amount != expected {
+        return Err(format!(
+            "expected a balance of {}, got {}",
+            expected, amount
+        ));
+    }
+    Ok(())
+}
+
+fn expect_failure(result: Result<Vec<String>, TransactionError>) -> CaseResult {
+    match result {
+        Ok(_) => Err("instruction succeeded but should have failed".to_string()),
+        Err(_) => Ok(()),
+    }
+}
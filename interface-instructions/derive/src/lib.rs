@@ -4,14 +4,63 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::ToTokens;
-use spl_interface_instructions_syn::InterfaceInstructionBuilder;
+use spl_interface_instructions_syn::{
+    InterfaceAccountBuilder, InterfaceArgsBuilder, InterfaceErrorBuilder, InterfaceEventBuilder,
+    InterfaceInstructionBuilder,
+};
 use syn::parse_macro_input;
 
 /// Proc macro attribute for defining a Solana program interface
 /// in native or Shank programs
-#[proc_macro_derive(SplInterfaceInstruction, attributes(interface))]
+#[proc_macro_derive(
+    SplInterfaceInstruction,
+    attributes(
+        implements,
+        interface,
+        interface_arg,
+        interface_canonical,
+        interface_legacy_discriminators,
+        interface_unimplemented
+    )
+)]
 pub fn spl_interface_instruction(input: TokenStream) -> TokenStream {
     parse_macro_input!(input as InterfaceInstructionBuilder)
         .to_token_stream()
         .into()
 }
+
+/// Derive macro for a struct used as an instruction's payload
+/// (`Variant(Args)`), so that its fields can be checked against
+/// the interface instruction
+#[proc_macro_derive(SplInterfaceArgs, attributes(interface_arg))]
+pub fn spl_interface_args(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as InterfaceArgsBuilder)
+        .to_token_stream()
+        .into()
+}
+
+/// Derive macro for an account struct implementing an interface
+/// account layout
+#[proc_macro_derive(SplInterfaceAccount, attributes(interface_account, interface_arg))]
+pub fn spl_interface_account(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as InterfaceAccountBuilder)
+        .to_token_stream()
+        .into()
+}
+
+/// Derive macro for an event struct implementing an interface event
+#[proc_macro_derive(SplInterfaceEvent, attributes(interface_event, interface_arg))]
+pub fn spl_interface_event(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as InterfaceEventBuilder)
+        .to_token_stream()
+        .into()
+}
+
+/// Derive macro mapping a program's error enum onto the error codes
+/// defined by its interfaces
+#[proc_macro_derive(SplInterfaceError, attributes(interface_error))]
+pub fn spl_interface_error(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as InterfaceErrorBuilder)
+        .to_token_stream()
+        .into()
+}
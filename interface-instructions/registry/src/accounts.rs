@@ -0,0 +1,83 @@
+//! Account-state interfaces: standard account layouts that generic
+//! readers can rely on, each prefixed with an 8-byte account
+//! discriminator
+
+use syn::ItemStruct;
+
+use crate::{
+    error::SplInterfaceError,
+    layout::{self, StructKind},
+    qualified_namespace, RequiredArg,
+};
+
+/// An account layout defined by an interface
+///
+/// Accounts always allow fields after the required ones, since readers
+/// only rely on the interface's prefix of the layout
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceAccount {
+    /// The interface's namespace
+    pub interface_namespace: String,
+    /// The interface's version
+    pub interface_version: u8,
+    /// The account's namespace
+    pub account_namespace: String,
+    /// The account's required fields, in layout order
+    pub required_fields: Vec<RequiredArg>,
+}
+impl InterfaceAccount {
+    /// Returns the 8-byte discriminator for the account
+    pub fn discriminator(&self) -> [u8; 8] {
+        layout::discriminator(&self.discriminator_preimage())
+    }
+    /// Returns the string hashed to build the discriminator
+    /// (ie. `srfc20_token:account:TokenAccount`)
+    pub fn discriminator_preimage(&self) -> String {
+        layout::discriminator_preimage(
+            StructKind::Account,
+            &self.interface_namespace,
+            self.interface_version,
+            &self.account_namespace,
+        )
+    }
+    /// Returns the interface's namespace qualified with its version
+    pub fn qualified_interface_namespace(&self) -> String {
+        qualified_namespace(&self.interface_namespace, self.interface_version)
+    }
+    /// Converts an account namespace and `&ItemStruct` to an
+    /// `InterfaceAccount` for evaluation
+    pub fn from_item_struct(
+        interface_namespace: &String,
+        interface_version: u8,
+        account_namespace: &String,
+        item_struct: &ItemStruct,
+    ) -> syn::Result<Self> {
+        let required_fields = layout::struct_fields(
+            StructKind::Account,
+            interface_namespace,
+            interface_version,
+            account_namespace,
+            item_struct,
+        )?;
+        Ok(Self {
+            interface_namespace: interface_namespace.to_string(),
+            interface_version,
+            account_namespace: account_namespace.to_string(),
+            required_fields,
+        })
+    }
+}
+
+/// Evaluates a program's account struct against the interface account
+/// it claims to implement
+pub fn evaluate_interface_account(
+    declared_account: &InterfaceAccount,
+) -> Result<(), SplInterfaceError> {
+    layout::evaluate_struct(
+        StructKind::Account,
+        &declared_account.interface_namespace,
+        declared_account.interface_version,
+        &declared_account.account_namespace,
+        &declared_account.required_fields,
+    )
+}
@@ -0,0 +1,177 @@
+//! A compact binary encoding of the interface manifest, embedded in a
+//! program's ELF so that the interfaces a deployed binary claims can
+//! be read without its source (similar to `solana-security-txt`)
+//!
+//! The encoding is framed by `MANIFEST_BEGIN` and `MANIFEST_END`, so
+//! the reader only has to scan the binary for the markers:
+//!
+//! ```text
+//! MANIFEST_BEGIN
+//! u8 interface count
+//!   u8 namespace length, namespace, u8 version, u8 instruction count
+//!     u8 namespace length, namespace, [u8; 8] discriminator, u8 flags
+//! MANIFEST_END
+//! ```
+//!
+//! An instruction's flags hold whether it's optional in the lowest bit
+//! and its status in the two bits above
+
+use crate::manifest::{
+    InterfaceManifest, ManifestInstruction, ManifestInstructionStatus, ManifestInterface,
+};
+
+/// Marker preceding an embedded manifest
+pub const MANIFEST_BEGIN: &[u8] = b"=======BEGIN SPL INTERFACE MANIFEST V1=======\0";
+/// Marker following an embedded manifest
+pub const MANIFEST_END: &[u8] = b"=======END SPL INTERFACE MANIFEST=======\0";
+
+/// Errors that may be returned when reading an embedded manifest
+#[derive(Clone, Debug, Eq, thiserror::Error, PartialEq)]
+pub enum EmbeddedManifestError {
+    #[error("No embedded interface manifest found")]
+    NotFound,
+    #[error("Embedded interface manifest is malformed")]
+    Malformed,
+    #[error("Interface manifest has a namespace or count too large to encode")]
+    TooLarge,
+}
+
+/// Encodes a manifest, framed by its markers, for embedding in a
+/// program
+///
+/// Namespaces and counts are length-prefixed with a `u8`, so a manifest
+/// with any of them over 255 can't be encoded
+pub fn encode_manifest(manifest: &InterfaceManifest) -> Result<Vec<u8>, EmbeddedManifestError> {
+    let mut data = MANIFEST_BEGIN.to_vec();
+    data.push(encode_len(manifest.interfaces.len())?);
+    for interface in &manifest.interfaces {
+        push_str(&mut data, &interface.namespace)?;
+        data.push(interface.version);
+        data.push(encode_len(interface.instructions.len())?);
+        for instruction in &interface.instructions {
+            push_str(&mut data, &instruction.namespace)?;
+            data.extend_from_slice(&instruction.discriminator);
+            let status = match instruction.status {
+                ManifestInstructionStatus::Implemented => 0,
+                ManifestInstructionStatus::Omitted => 1,
+                ManifestInstructionStatus::Unsupported => 2,
+            };
+            data.push(instruction.optional as u8 | status << 1);
+        }
+    }
+    data.extend_from_slice(MANIFEST_END);
+    Ok(data)
+}
+
+/// Decodes a manifest framed by its markers
+pub fn decode_manifest(data: &[u8]) -> Result<InterfaceManifest, EmbeddedManifestError> {
+    let body = data
+        .strip_prefix(MANIFEST_BEGIN)
+        .and_then(|body| body.strip_suffix(MANIFEST_END))
+        .ok_or(EmbeddedManifestError::Malformed)?;
+    let mut reader = Reader(body);
+    let mut interfaces = vec![];
+    for _ in 0..reader.u8()? {
+        let namespace = reader.string()?;
+        let version = reader.u8()?;
+        let mut instructions = vec![];
+        for _ in 0..reader.u8()? {
+            let namespace = reader.string()?;
+            let mut discriminator = [0u8; 8];
+            discriminator.copy_from_slice(reader.bytes(8)?);
+            let flags = reader.u8()?;
+            let status = match flags >> 1 {
+                0 => ManifestInstructionStatus::Implemented,
+                1 => ManifestInstructionStatus::Omitted,
+                2 => ManifestInstructionStatus::Unsupported,
+                _ => return Err(EmbeddedManifestError::Malformed),
+            };
+            instructions.push(ManifestInstruction {
+                namespace,
+                discriminator,
+                optional: flags & 1 == 1,
+                status,
+            });
+        }
+        interfaces.push(ManifestInterface {
+            namespace,
+            version,
+            instructions,
+        });
+    }
+    if !reader.0.is_empty() {
+        return Err(EmbeddedManifestError::Malformed);
+    }
+    Ok(InterfaceManifest { interfaces })
+}
+
+/// Reads every manifest embedded in a program binary (ie. a `.so`
+/// file), in the order they appear
+///
+/// Markers that don't frame a valid manifest (ie. the marker constants
+/// themselves, if a binary links this module) are skipped
+pub fn read_embedded_manifests(
+    binary: &[u8],
+) -> Result<Vec<InterfaceManifest>, EmbeddedManifestError> {
+    let mut manifests = vec![];
+    let mut found_marker = false;
+    let mut rest = binary;
+    while let Some(begin) = find(rest, MANIFEST_BEGIN) {
+        found_marker = true;
+        let manifest = find(&rest[begin..], MANIFEST_END).and_then(|end| {
+            let end = begin + end + MANIFEST_END.len();
+            decode_manifest(&rest[begin..end]).ok().map(|m| (m, end))
+        });
+        match manifest {
+            Some((manifest, end)) => {
+                manifests.push(manifest);
+                rest = &rest[end..];
+            }
+            None => rest = &rest[begin + 1..],
+        }
+    }
+    match (manifests.is_empty(), found_marker) {
+        (false, _) => Ok(manifests),
+        (true, true) => Err(EmbeddedManifestError::Malformed),
+        (true, false) => Err(EmbeddedManifestError::NotFound),
+    }
+}
+
+/// Returns the offset of the first occurrence of `needle`
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Converts a length or count to its `u8` prefix
+fn encode_len(len: usize) -> Result<u8, EmbeddedManifestError> {
+    u8::try_from(len).map_err(|_| EmbeddedManifestError::TooLarge)
+}
+
+/// Appends a `u8` length-prefixed string
+fn push_str(data: &mut Vec<u8>, value: &str) -> Result<(), EmbeddedManifestError> {
+    data.push(encode_len(value.len())?);
+    data.extend_from_slice(value.as_bytes());
+    Ok(())
+}
+
+/// Cursor over the body of an embedded manifest
+struct Reader<'a>(&'a [u8]);
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], EmbeddedManifestError> {
+        if self.0.len() < len {
+            return Err(EmbeddedManifestError::Malformed);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+    fn u8(&mut self) -> Result<u8, EmbeddedManifestError> {
+        Ok(self.bytes(1)?[0])
+    }
+    fn string(&mut self) -> Result<String, EmbeddedManifestError> {
+        let len = self.u8()? as usize;
+        String::from_utf8(self.bytes(len)?.to_vec()).map_err(|_| EmbeddedManifestError::Malformed)
+    }
+}
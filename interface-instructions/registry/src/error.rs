@@ -1,15 +1,69 @@
 //! Errors for the SPL interface instruction parser.
 
+use solana_program::program_error::ProgramError;
+
 #[derive(Clone, Debug, Eq, thiserror::Error, PartialEq)]
 pub enum SplInterfaceError {
     #[error("Error parsing interface attribute")]
     ParseError,
     #[error("Invalid interface namespace")]
     InvalidInterfaceNamespace,
+    #[error("Invalid interface version")]
+    InvalidInterfaceVersion,
     #[error("Missing required instruction for interface")]
     InstructionMissing,
     #[error("Instruction not found")]
     InstructionNotFound,
     #[error("Missing argument(s) for instruction")]
     MissingArgument,
+    #[error("Missing interface required by a declared interface")]
+    RequiredInterfaceMissing,
+    #[error("Instruction declared as unimplemented is also implemented")]
+    ConflictingUnimplementedInstruction,
+    #[error("Interface declared with `implements` has no annotated instructions")]
+    DeclaredInterfaceNotImplemented,
+    #[error("Annotated instruction belongs to an interface not declared with `implements`")]
+    UndeclaredInterface,
+    #[error("Instruction with several interface annotations needs a canonical one for packing")]
+    CanonicalInterfaceMissing,
+    #[error("Legacy discriminators require the `legacy-discriminators` feature")]
+    LegacyDiscriminatorsDisabled,
+    #[error("Interface discriminator is ambiguous with a legacy borsh variant tag")]
+    AmbiguousLegacyDiscriminator,
+    #[error("Account not found")]
+    AccountNotFound,
+    #[error("Incorrect field(s) for account")]
+    AccountFieldMismatch,
+    #[error("Event not found")]
+    EventNotFound,
+    #[error("Incorrect field(s) for event")]
+    EventFieldMismatch,
+    #[error("Interface error not found")]
+    ErrorNotFound,
+    #[error("Interface lockfile is malformed")]
+    LockfileMalformed,
+    #[error("Interface wire format does not match the lockfile")]
+    LockfileMismatch,
+    #[error("Interface lockfile could not be written")]
+    LockfileUnwritable,
+}
+
+/// Errors returned on-chain by code generated for interface
+/// instructions
+#[derive(Clone, Copy, Debug, Eq, thiserror::Error, PartialEq)]
+#[repr(u32)]
+pub enum InterfaceProgramError {
+    /// The program deliberately does not support this interface
+    /// instruction (`#[interface_unimplemented(..)]`)
+    #[error("Interface instruction is not supported by this program")]
+    UnsupportedInterfaceInstruction = 0xFFFF_FF00,
+    /// The interface does not define the instruction being looked up
+    #[error("Interface does not define this instruction")]
+    UnknownInterfaceInstruction = 0xFFFF_FF01,
+}
+
+impl From<InterfaceProgramError> for ProgramError {
+    fn from(e: InterfaceProgramError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
 }
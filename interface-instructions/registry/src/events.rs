@@ -0,0 +1,133 @@
+//! Event interfaces: standard events emitted with `sol_log_data`, each
+//! prefixed with an 8-byte event discriminator, plus a parser that
+//! recovers them from transaction log messages
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_program::pubkey::Pubkey;
+use syn::ItemStruct;
+
+use crate::{
+    error::SplInterfaceError,
+    layout::{self, StructKind},
+    qualified_namespace, InterfaceEventPack, RequiredArg,
+};
+
+/// An event defined by an interface
+///
+/// Events always allow fields after the required ones, since indexers
+/// only rely on the interface's prefix of the event
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceEvent {
+    /// The interface's namespace
+    pub interface_namespace: String,
+    /// The interface's version
+    pub interface_version: u8,
+    /// The event's namespace
+    pub event_namespace: String,
+    /// The event's required fields, in order
+    pub required_fields: Vec<RequiredArg>,
+}
+impl InterfaceEvent {
+    /// Returns the 8-byte discriminator for the event
+    pub fn discriminator(&self) -> [u8; 8] {
+        layout::discriminator(&self.discriminator_preimage())
+    }
+    /// Returns the string hashed to build the discriminator
+    /// (ie. `srfc20_token:event:Transfer`)
+    pub fn discriminator_preimage(&self) -> String {
+        layout::discriminator_preimage(
+            StructKind::Event,
+            &self.interface_namespace,
+            self.interface_version,
+            &self.event_namespace,
+        )
+    }
+    /// Returns the interface's namespace qualified with its version
+    pub fn qualified_interface_namespace(&self) -> String {
+        qualified_namespace(&self.interface_namespace, self.interface_version)
+    }
+    /// Converts an event namespace and `&ItemStruct` to an
+    /// `InterfaceEvent` for evaluation
+    pub fn from_item_struct(
+        interface_namespace: &String,
+        interface_version: u8,
+        event_namespace: &String,
+        item_struct: &ItemStruct,
+    ) -> syn::Result<Self> {
+        let required_fields = layout::struct_fields(
+            StructKind::Event,
+            interface_namespace,
+            interface_version,
+            event_namespace,
+            item_struct,
+        )?;
+        Ok(Self {
+            interface_namespace: interface_namespace.to_string(),
+            interface_version,
+            event_namespace: event_namespace.to_string(),
+            required_fields,
+        })
+    }
+}
+
+/// Evaluates a program's event struct against the interface event it
+/// claims to implement
+pub fn evaluate_interface_event(declared_event: &InterfaceEvent) -> Result<(), SplInterfaceError> {
+    layout::evaluate_struct(
+        StructKind::Event,
+        &declared_event.interface_namespace,
+        declared_event.interface_version,
+        &declared_event.event_namespace,
+        &declared_event.required_fields,
+    )
+}
+
+/// Prefix of the log message written by `sol_log_data`
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Extracts the data logged with `sol_log_data` from a transaction's
+/// log messages, along with the program that logged it
+///
+/// Each `sol_log_data` call's fields are concatenated, since events
+/// are emitted as a single field
+pub fn logged_data(logs: &[String]) -> Vec<(Pubkey, Vec<u8>)> {
+    let mut invoked: Vec<Pubkey> = vec![];
+    let mut data = vec![];
+    for log in logs {
+        if let Some(encoded) = log.strip_prefix(PROGRAM_DATA) {
+            let decoded: Option<Vec<u8>> = encoded
+                .split(' ')
+                .map(|field| STANDARD.decode(field).ok())
+                .collect::<Option<Vec<_>>>()
+                .map(|fields| fields.concat());
+            if let (Some(program_id), Some(decoded)) = (invoked.last(), decoded) {
+                data.push((*program_id, decoded));
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            let program_id = words.next().and_then(|id| id.parse::<Pubkey>().ok());
+            match (program_id, words.next()) {
+                (Some(program_id), Some("invoke")) => invoked.push(program_id),
+                (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                    invoked.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    data
+}
+
+/// Decodes every event of type `E` from a transaction's log messages,
+/// optionally only those emitted by a given program
+pub fn parse_events<E: InterfaceEventPack>(logs: &[String], program_id: Option<&Pubkey>) -> Vec<E> {
+    logged_data(logs)
+        .into_iter()
+        .filter(|(emitter, _)| match program_id {
+            Some(program_id) => program_id == emitter,
+            None => true,
+        })
+        .filter(|(_, data)| data.starts_with(&E::DISCRIMINATOR))
+        .filter_map(|(_, data)| E::unpack(&data).ok())
+        .collect()
+}
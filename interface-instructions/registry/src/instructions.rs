@@ -1,26 +1,150 @@
 //! Community-defined interfaces from sRFC workflow
 
-use crate::{Interface, InterfaceInstruction, RequiredArgType};
+#[cfg(feature = "example-interfaces")]
+use crate::interface_errors::InterfaceError;
+use crate::{
+    accounts::InterfaceAccount, events::InterfaceEvent, Interface, InterfaceInstruction,
+    InterfaceInstructionAccount, RequiredArgType, TrailingArgsPolicy,
+};
 
 /// The sRFC 20 Token Interface
+///
+/// Instructions may append extension arguments (ie. a transfer `memo`)
 pub struct SRFC20 {}
 impl Interface for SRFC20 {
     const NAMESPACE: &'static str = "srfc20_token";
+    const TRAILING_ARGS: TrailingArgsPolicy = TrailingArgsPolicy::Allow;
 
     fn instructions() -> Vec<InterfaceInstruction> {
         vec![
             InterfaceInstruction {
                 interface_namespace: Self::NAMESPACE.to_string(),
+                interface_version: Self::VERSION,
                 instruction_namespace: "mint_to".to_string(),
                 required_args: vec![("amount".to_string(), RequiredArgType::U64)],
+                optional: false,
+                returns: vec![],
             },
             InterfaceInstruction {
                 interface_namespace: Self::NAMESPACE.to_string(),
+                interface_version: Self::VERSION,
                 instruction_namespace: "transfer".to_string(),
                 required_args: vec![("amount".to_string(), RequiredArgType::U64)],
+                optional: false,
+                returns: vec![],
             },
         ]
     }
+
+    fn instruction_accounts(instruction_namespace: &str) -> Vec<InterfaceInstructionAccount> {
+        match instruction_namespace {
+            "mint_to" => vec![
+                InterfaceInstructionAccount::writable("mint"),
+                InterfaceInstructionAccount::writable("destination"),
+                InterfaceInstructionAccount::signer("authority"),
+            ],
+            "transfer" => vec![
+                InterfaceInstructionAccount::readonly("mint"),
+                InterfaceInstructionAccount::writable("recipient"),
+                InterfaceInstructionAccount::writable("from"),
+                InterfaceInstructionAccount::signer("authority"),
+            ],
+            _ => vec![],
+        }
+    }
+
+    fn accounts() -> Vec<InterfaceAccount> {
+        vec![InterfaceAccount {
+            interface_namespace: Self::NAMESPACE.to_string(),
+            interface_version: Self::VERSION,
+            account_namespace: "token_account".to_string(),
+            required_fields: vec![
+                ("mint".to_string(), RequiredArgType::Pubkey),
+                ("owner".to_string(), RequiredArgType::Pubkey),
+                ("amount".to_string(), RequiredArgType::U64),
+            ],
+        }]
+    }
+
+    fn events() -> Vec<InterfaceEvent> {
+        vec![InterfaceEvent {
+            interface_namespace: Self::NAMESPACE.to_string(),
+            interface_version: Self::VERSION,
+            event_namespace: "transfer".to_string(),
+            required_fields: vec![
+                ("from".to_string(), RequiredArgType::Pubkey),
+                ("to".to_string(), RequiredArgType::Pubkey),
+                ("amount".to_string(), RequiredArgType::U64),
+            ],
+        }]
+    }
+}
+
+/// An example version 2 of the sRFC 20 Token Interface, which isn't an
+/// accepted sRFC and only serves to exercise interface versioning
+/// (`example-interfaces` feature)
+///
+/// A superset of version 1 adding `approve`, which keeps the version 1
+/// discriminators so that version 2 programs remain version 1 compliant
+#[cfg(feature = "example-interfaces")]
+pub struct SRFC20V2 {}
+#[cfg(feature = "example-interfaces")]
+impl Interface for SRFC20V2 {
+    const NAMESPACE: &'static str = SRFC20::NAMESPACE;
+    const VERSION: u8 = 2;
+    const STABLE_DISCRIMINATORS: bool = true;
+    const EXTENDS: Option<u8> = Some(SRFC20::VERSION);
+    const TRAILING_ARGS: TrailingArgsPolicy = SRFC20::TRAILING_ARGS;
+
+    fn instructions() -> Vec<InterfaceInstruction> {
+        let mut instructions: Vec<InterfaceInstruction> = SRFC20::instructions()
+            .into_iter()
+            .map(|ix| InterfaceInstruction {
+                interface_version: Self::VERSION,
+                ..ix
+            })
+            .collect();
+        instructions.push(InterfaceInstruction {
+            interface_namespace: Self::NAMESPACE.to_string(),
+            interface_version: Self::VERSION,
+            instruction_namespace: "approve".to_string(),
+            required_args: vec![("amount".to_string(), RequiredArgType::U64)],
+            optional: false,
+            returns: vec![],
+        });
+        instructions
+    }
+
+    fn instruction_accounts(instruction_namespace: &str) -> Vec<InterfaceInstructionAccount> {
+        match instruction_namespace {
+            "approve" => vec![
+                InterfaceInstructionAccount::writable("source"),
+                InterfaceInstructionAccount::readonly("delegate"),
+                InterfaceInstructionAccount::signer("authority"),
+            ],
+            _ => SRFC20::instruction_accounts(instruction_namespace),
+        }
+    }
+
+    fn accounts() -> Vec<InterfaceAccount> {
+        SRFC20::accounts()
+            .into_iter()
+            .map(|account| InterfaceAccount {
+                interface_version: Self::VERSION,
+                ..account
+            })
+            .collect()
+    }
+
+    fn events() -> Vec<InterfaceEvent> {
+        SRFC20::events()
+            .into_iter()
+            .map(|event| InterfaceEvent {
+                interface_version: Self::VERSION,
+                ..event
+            })
+            .collect()
+    }
 }
 
 /// The sRFC 21 Token Interface
@@ -31,58 +155,195 @@ impl Interface for SRFC21 {
     fn instructions() -> Vec<InterfaceInstruction> {
         vec![InterfaceInstruction {
             interface_namespace: Self::NAMESPACE.to_string(),
+            interface_version: Self::VERSION,
             instruction_namespace: "burn".to_string(),
             required_args: vec![("amount".to_string(), RequiredArgType::U64)],
+            optional: false,
+            returns: vec![],
         }]
     }
+
+    fn instruction_accounts(instruction_namespace: &str) -> Vec<InterfaceInstructionAccount> {
+        match instruction_namespace {
+            "burn" => vec![
+                InterfaceInstructionAccount::writable("mint"),
+                InterfaceInstructionAccount::writable("from"),
+                InterfaceInstructionAccount::signer("authority"),
+            ],
+            _ => vec![],
+        }
+    }
 }
 
 /// The sRFC 22 Associated Token Interface
+///
+/// Requires the sRFC 20 Token Interface
 pub struct SRFC22 {}
 impl Interface for SRFC22 {
     const NAMESPACE: &'static str = "srfc22_associated_token";
 
+    fn requires() -> Vec<&'static str> {
+        vec![SRFC20::NAMESPACE]
+    }
+
     fn instructions() -> Vec<InterfaceInstruction> {
         vec![
             InterfaceInstruction {
                 interface_namespace: Self::NAMESPACE.to_string(),
+                interface_version: Self::VERSION,
                 instruction_namespace: "freeze".to_string(),
                 required_args: vec![],
+                optional: false,
+                returns: vec![],
             },
             InterfaceInstruction {
                 interface_namespace: Self::NAMESPACE.to_string(),
+                interface_version: Self::VERSION,
                 instruction_namespace: "thaw".to_string(),
                 required_args: vec![],
+                optional: false,
+                returns: vec![],
             },
         ]
     }
+
+    fn instruction_accounts(instruction_namespace: &str) -> Vec<InterfaceInstructionAccount> {
+        match instruction_namespace {
+            "freeze" | "thaw" => vec![
+                InterfaceInstructionAccount::readonly("mint"),
+                InterfaceInstructionAccount::writable("target"),
+                InterfaceInstructionAccount::signer("authority"),
+            ],
+            _ => vec![],
+        }
+    }
 }
 
 /// The sRFC 23 Token Metadata Interface
+///
+/// `update_metadata` is optional, since metadata may be immutable
+///
+/// Requires the sRFC 20 Token Interface
 pub struct SRFC23 {}
 impl Interface for SRFC23 {
     const NAMESPACE: &'static str = "srfc23_token_metadata";
 
+    fn requires() -> Vec<&'static str> {
+        vec![SRFC20::NAMESPACE]
+    }
+
     fn instructions() -> Vec<InterfaceInstruction> {
         vec![
             InterfaceInstruction {
                 interface_namespace: Self::NAMESPACE.to_string(),
+                interface_version: Self::VERSION,
                 instruction_namespace: "create_metadata".to_string(),
                 required_args: vec![
                     ("name".to_string(), RequiredArgType::String),
                     ("symbol".to_string(), RequiredArgType::String),
                     ("uri".to_string(), RequiredArgType::String),
                 ],
+                optional: false,
+                returns: vec![],
             },
             InterfaceInstruction {
                 interface_namespace: Self::NAMESPACE.to_string(),
+                interface_version: Self::VERSION,
                 instruction_namespace: "update_metadata".to_string(),
                 required_args: vec![
                     ("name".to_string(), RequiredArgType::String),
                     ("symbol".to_string(), RequiredArgType::String),
                     ("uri".to_string(), RequiredArgType::String),
                 ],
+                optional: true,
+                returns: vec![],
+            },
+        ]
+    }
+
+    fn instruction_accounts(instruction_namespace: &str) -> Vec<InterfaceInstructionAccount> {
+        match instruction_namespace {
+            "create_metadata" => vec![
+                InterfaceInstructionAccount::readonly("mint"),
+                InterfaceInstructionAccount::writable("metadata"),
+                InterfaceInstructionAccount::signer("authority"),
+            ],
+            "update_metadata" => vec![
+                InterfaceInstructionAccount::writable("metadata"),
+                InterfaceInstructionAccount::signer("authority"),
+            ],
+            _ => vec![],
+        }
+    }
+}
+
+/// An example interface extending the sRFC 20 Token and sRFC 23 Token
+/// Metadata Interfaces with views and an error table, which aren't part
+/// of any accepted sRFC and only serve to exercise views and interface
+/// errors (`example-interfaces` feature)
+///
+/// Both views are optional, so that a program can implement either one
+///
+/// Requires the sRFC 20 Token Interface
+#[cfg(feature = "example-interfaces")]
+pub struct ExampleTokenExtensions {}
+#[cfg(feature = "example-interfaces")]
+impl Interface for ExampleTokenExtensions {
+    const NAMESPACE: &'static str = "example_token_extensions";
+
+    fn requires() -> Vec<&'static str> {
+        vec![SRFC20::NAMESPACE]
+    }
+
+    fn instructions() -> Vec<InterfaceInstruction> {
+        vec![
+            InterfaceInstruction {
+                interface_namespace: Self::NAMESPACE.to_string(),
+                interface_version: Self::VERSION,
+                instruction_namespace: "balance_of".to_string(),
+                required_args: vec![("owner".to_string(), RequiredArgType::Pubkey)],
+                optional: true,
+                returns: vec![RequiredArgType::U64],
+            },
+            InterfaceInstruction {
+                interface_namespace: Self::NAMESPACE.to_string(),
+                interface_version: Self::VERSION,
+                instruction_namespace: "get_metadata".to_string(),
+                required_args: vec![],
+                optional: true,
+                returns: vec![
+                    RequiredArgType::String,
+                    RequiredArgType::String,
+                    RequiredArgType::String,
+                ],
             },
         ]
     }
+
+    fn instruction_accounts(instruction_namespace: &str) -> Vec<InterfaceInstructionAccount> {
+        match instruction_namespace {
+            "balance_of" => vec![InterfaceInstructionAccount::readonly("token_account")],
+            "get_metadata" => vec![InterfaceInstructionAccount::readonly("metadata")],
+            _ => vec![],
+        }
+    }
+
+    fn errors() -> Vec<InterfaceError> {
+        [
+            ("insufficient_funds", "Insufficient funds"),
+            ("account_frozen", "Account is frozen"),
+            ("owner_mismatch", "Owner does not match"),
+            ("mint_mismatch", "Mint does not match"),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(offset, (error_namespace, message))| InterfaceError {
+            interface_namespace: Self::NAMESPACE.to_string(),
+            interface_version: Self::VERSION,
+            error_namespace: error_namespace.to_string(),
+            offset: offset as u8,
+            message: message.to_string(),
+        })
+        .collect()
+    }
 }
@@ -0,0 +1,93 @@
+//! Standardized interface errors, so that callers can react to an
+//! interface instruction's failure regardless of which program
+//! implements it
+//!
+//! Each interface owns a range of 256 `ProgramError::Custom` codes
+//! derived from its namespace hash, shared by all of its versions
+
+use solana_program::program_error::ProgramError;
+
+use crate::{
+    error::SplInterfaceError, qualified_namespace, registered_interface, registered_interfaces,
+    unknown_interface_error,
+};
+
+/// An error defined by an interface
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceError {
+    /// The interface's namespace
+    pub interface_namespace: String,
+    /// The interface's version
+    pub interface_version: u8,
+    /// The error's namespace
+    pub error_namespace: String,
+    /// The error's offset within the interface's range of codes
+    pub offset: u8,
+    /// A human-readable description of the error
+    pub message: String,
+}
+impl InterfaceError {
+    /// Returns the error's `ProgramError::Custom` code
+    pub fn code(&self) -> u32 {
+        error_code_base(&self.interface_namespace) + self.offset as u32
+    }
+    /// Returns the interface's namespace qualified with its version
+    pub fn qualified_interface_namespace(&self) -> String {
+        qualified_namespace(&self.interface_namespace, self.interface_version)
+    }
+}
+
+/// Returns the first code of an interface's range of error codes
+///
+/// Ranges sit in `0x4000_0000..0x8000_0000`, clear of the small codes
+/// programs usually pick and of `InterfaceProgramError`
+pub fn error_code_base(interface_namespace: &str) -> u32 {
+    let hash = solana_program::hash::hash(interface_namespace.as_bytes()).to_bytes();
+    let hash = u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]);
+    0x4000_0000 | (hash & 0x3FFF_FF00)
+}
+
+/// Looks up an interface error by its qualified interface namespace
+/// and error namespace, for mapping a program's error onto it
+pub fn find_interface_error(
+    qualified_interface_namespace: &str,
+    interface_namespace: &str,
+    error_namespace: &str,
+) -> Result<InterfaceError, SplInterfaceError> {
+    let interface = registered_interface(qualified_interface_namespace)
+        .ok_or_else(|| unknown_interface_error(interface_namespace))?;
+    match (interface.errors)()
+        .into_iter()
+        .find(|e| e.error_namespace == error_namespace)
+    {
+        Some(error) => Ok(error),
+        None => {
+            println!("\n\nFound the following unknown interface errors:\n");
+            println!("  - {}::{}", qualified_interface_namespace, error_namespace);
+            Err(SplInterfaceError::ErrorNotFound)
+        }
+    }
+}
+
+/// Decodes a `ProgramError::Custom` code returned by any program into
+/// the interface error it stands for, if any
+///
+/// Versions of an interface share their codes, so the earliest
+/// version defining the error is returned
+pub fn decode_error_code(code: u32) -> Option<InterfaceError> {
+    let mut interfaces = registered_interfaces();
+    interfaces.sort_by_key(|interface| interface.version);
+    interfaces
+        .into_iter()
+        .flat_map(|interface| (interface.errors)())
+        .find(|error| error.code() == code)
+}
+
+/// Decodes a `ProgramError` into the interface error it stands for,
+/// if any
+pub fn decode_program_error(error: &ProgramError) -> Option<InterfaceError> {
+    match error {
+        ProgramError::Custom(code) => decode_error_code(*code),
+        _ => None,
+    }
+}
@@ -0,0 +1,100 @@
+//! Instructions sysvar introspection keyed by interface discriminators,
+//! so that a program can check what other interface instructions in the
+//! same transaction did, for any of the compliant programs it trusts
+//!
+//! Discriminators are public, so any program can accept data that looks
+//! like an interface instruction: callers must list the programs whose
+//! instructions they accept
+//!
+//! Only top-level instructions are listed in the sysvar, so
+//! instructions invoked through a CPI aren't found
+
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::{error::InterfaceProgramError, Interface};
+
+/// A top-level instruction of the transaction that calls an interface
+/// instruction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceIxMatch {
+    /// The instruction's index in the transaction
+    pub index: usize,
+    /// The instruction, sent to whichever program implements the
+    /// interface
+    pub instruction: Instruction,
+    /// The names of the accounts the interface expects, in order
+    account_names: Vec<&'static str>,
+}
+impl InterfaceIxMatch {
+    /// Decodes the instruction's arguments, following its 8-byte
+    /// discriminator
+    ///
+    /// Any trailing extension arguments are ignored, so `T` only needs
+    /// to declare the interface's required arguments
+    pub fn args<T: BorshDeserialize>(&self) -> Result<T, ProgramError> {
+        T::deserialize(&mut &self.instruction.data[8..])
+            .map_err(|_| ProgramError::InvalidInstructionData)
+    }
+    /// Looks up one of the instruction's accounts by the name the
+    /// interface gives it (ie. `recipient` for `srfc20_token::transfer`)
+    pub fn account(&self, name: &str) -> Option<&AccountMeta> {
+        self.account_names
+            .iter()
+            .position(|account_name| *account_name == name)
+            .and_then(|index| self.instruction.accounts.get(index))
+    }
+}
+
+/// Finds every top-level instruction calling an interface instruction
+/// on one of `program_ids` before the current one, in transaction order
+pub fn find_preceding_interface_ixs<I: Interface>(
+    instruction_namespace: &str,
+    program_ids: &[Pubkey],
+    instructions_sysvar: &AccountInfo,
+) -> Result<Vec<InterfaceIxMatch>, ProgramError> {
+    let interface_ix = I::instructions()
+        .into_iter()
+        .find(|ix| ix.instruction_namespace == instruction_namespace)
+        .ok_or(InterfaceProgramError::UnknownInterfaceInstruction)?;
+    let discriminator = interface_ix.discriminator();
+    let account_names: Vec<&'static str> = I::instruction_accounts(instruction_namespace)
+        .into_iter()
+        .map(|account| account.name)
+        .collect();
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let mut matches = vec![];
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index, instructions_sysvar)?;
+        if program_ids.contains(&instruction.program_id)
+            && instruction.data.len() >= 8
+            && instruction.data[..8] == discriminator
+        {
+            matches.push(InterfaceIxMatch {
+                index,
+                instruction,
+                account_names: account_names.clone(),
+            });
+        }
+    }
+    Ok(matches)
+}
+
+/// Finds the closest top-level instruction calling an interface
+/// instruction on one of `program_ids` before the current one
+pub fn find_preceding_interface_ix<I: Interface>(
+    instruction_namespace: &str,
+    program_ids: &[Pubkey],
+    instructions_sysvar: &AccountInfo,
+) -> Result<Option<InterfaceIxMatch>, ProgramError> {
+    Ok(
+        find_preceding_interface_ixs::<I>(instruction_namespace, program_ids, instructions_sysvar)?
+            .pop(),
+    )
+}
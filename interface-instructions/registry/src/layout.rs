@@ -0,0 +1,156 @@
+//! Helpers shared by the discriminator-prefixed structs an interface
+//! defines (accounts and events), which only differ by their kind
+
+use syn::ItemStruct;
+
+use crate::{
+    compare_args, discriminator_domain, error::SplInterfaceError, interface_arg_name,
+    print_arg_mismatches, qualified_namespace, registered_interface, to_pascal_case,
+    unknown_interface_error, RegisteredInterface, RequiredArg, RequiredArgType, TrailingArgsPolicy,
+};
+
+/// The kind of a discriminator-prefixed interface struct
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum StructKind {
+    /// An account layout
+    Account,
+    /// An event
+    Event,
+}
+impl StructKind {
+    /// The kind's prefix, used in discriminator preimages and
+    /// diagnostics
+    fn prefix(&self) -> &'static str {
+        match self {
+            StructKind::Account => "account",
+            StructKind::Event => "event",
+        }
+    }
+    /// Returns the required fields of the interface's struct of this
+    /// kind with the provided namespace, if any
+    fn interface_fields(
+        &self,
+        interface: &RegisteredInterface,
+        namespace: &str,
+    ) -> Option<Vec<RequiredArg>> {
+        match self {
+            StructKind::Account => (interface.accounts)()
+                .into_iter()
+                .find(|a| a.account_namespace == namespace)
+                .map(|a| a.required_fields),
+            StructKind::Event => (interface.events)()
+                .into_iter()
+                .find(|e| e.event_namespace == namespace)
+                .map(|e| e.required_fields),
+        }
+    }
+    fn not_found_error(&self) -> SplInterfaceError {
+        match self {
+            StructKind::Account => SplInterfaceError::AccountNotFound,
+            StructKind::Event => SplInterfaceError::EventNotFound,
+        }
+    }
+    fn mismatch_error(&self) -> SplInterfaceError {
+        match self {
+            StructKind::Account => SplInterfaceError::AccountFieldMismatch,
+            StructKind::Event => SplInterfaceError::EventFieldMismatch,
+        }
+    }
+}
+
+/// Returns the 8-byte discriminator hashed from a preimage
+pub(crate) fn discriminator(preimage: &str) -> [u8; 8] {
+    let mut disc = [0u8; 8];
+    disc.copy_from_slice(&solana_program::hash::hash(preimage.as_bytes()).to_bytes()[..8]);
+    disc
+}
+
+/// Returns the string hashed to build a struct's discriminator
+/// (ie. `srfc20_token:account:TokenAccount`)
+pub(crate) fn discriminator_preimage(
+    kind: StructKind,
+    interface_namespace: &str,
+    interface_version: u8,
+    namespace: &str,
+) -> String {
+    format!(
+        "{}:{}:{}",
+        discriminator_domain(interface_namespace, interface_version),
+        kind.prefix(),
+        to_pascal_case(namespace)
+    )
+}
+
+/// Reads the fields of a struct for evaluation against the interface's
+/// required fields
+///
+/// Only the fields covering the interface's required prefix are read,
+/// since the fields after it may have any type
+pub(crate) fn struct_fields(
+    kind: StructKind,
+    interface_namespace: &str,
+    interface_version: u8,
+    namespace: &str,
+    item_struct: &ItemStruct,
+) -> syn::Result<Vec<RequiredArg>> {
+    let required_len =
+        registered_interface(&qualified_namespace(interface_namespace, interface_version))
+            .and_then(|interface| kind.interface_fields(&interface, namespace))
+            .map_or(0, |fields| fields.len());
+    let mut fields = vec![];
+    for field in item_struct.fields.iter().take(required_len) {
+        let name = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => String::new(),
+        };
+        let ty = RequiredArgType::try_from_type(&field.ty).ok_or_else(|| {
+            syn::Error::new_spanned(
+                &field.ty,
+                format!("Unsupported type for an interface {} field", kind.prefix()),
+            )
+        })?;
+        fields.push((interface_arg_name(&field.attrs)?.unwrap_or(name), ty));
+    }
+    Ok(fields)
+}
+
+/// Evaluates a program's struct against the interface struct of the
+/// same kind it claims to implement
+pub(crate) fn evaluate_struct(
+    kind: StructKind,
+    interface_namespace: &str,
+    interface_version: u8,
+    namespace: &str,
+    declared_fields: &[RequiredArg],
+) -> Result<(), SplInterfaceError> {
+    let qualified_interface_namespace = qualified_namespace(interface_namespace, interface_version);
+    let interface = registered_interface(&qualified_interface_namespace)
+        .ok_or_else(|| unknown_interface_error(interface_namespace))?;
+    let interface_fields = match kind.interface_fields(&interface, namespace) {
+        Some(interface_fields) => interface_fields,
+        None => {
+            println!(
+                "\n\nFound the following unknown interface {}s:\n",
+                kind.prefix()
+            );
+            println!("  - {}::{}", qualified_interface_namespace, namespace);
+            return Err(kind.not_found_error());
+        }
+    };
+    let mismatches = compare_args(
+        &interface_fields,
+        declared_fields,
+        TrailingArgsPolicy::Allow,
+    );
+    if !mismatches.is_empty() {
+        println!(
+            "\n\nIncorrect fields for interface {} `{}::{}`:\n",
+            kind.prefix(),
+            qualified_interface_namespace,
+            namespace
+        );
+        print_arg_mismatches("fields", declared_fields, &interface_fields, &mismatches);
+        return Err(kind.mismatch_error());
+    }
+    Ok(())
+}
@@ -4,15 +4,31 @@
 //! Also provides the collection of currently accepted
 //! sRFC interfaces
 
+pub mod accounts;
+pub mod embedded;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod interface_errors;
+pub mod introspection;
+mod layout;
+pub mod lockfile;
+pub mod manifest;
+pub mod views;
 
 use solana_program::program_error::ProgramError;
-use std::collections::{HashMap, HashSet};
-use syn::{parse_quote, ItemFn, Type, Variant};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+use syn::{parse_quote, Attribute, Ident, ItemFn, Type, Variant};
 
+use accounts::InterfaceAccount;
 use error::SplInterfaceError;
+use events::InterfaceEvent;
 use instructions::*;
+use interface_errors::InterfaceError;
+use manifest::*;
 
 /// Trait for implementing Shank & Native programs to
 /// build a processor
@@ -26,12 +42,80 @@ pub trait InterfaceInstructionPack: Sized {
     fn pack<W: std::io::Write>(&self, writer: &mut W) -> Result<(), ProgramError>;
 }
 
+/// Trait for reading and writing account state that implements
+/// an interface account
+///
+/// The derive macro `#[derive(SplInterfaceAccount)]`
+/// will implement this trait for you
+pub trait InterfaceAccountPack: Sized {
+    /// The account's 8-byte interface discriminator
+    const DISCRIMINATOR: [u8; 8];
+    /// Unpacks the account from its data, checking the discriminator
+    fn unpack(data: &[u8]) -> Result<Self, ProgramError>;
+    /// Packs the account, prefixed with its discriminator
+    fn pack<W: std::io::Write>(&self, writer: &mut W) -> Result<(), ProgramError>;
+}
+
+/// Trait for emitting and decoding an event that implements an
+/// interface event
+///
+/// The derive macro `#[derive(SplInterfaceEvent)]`
+/// will implement this trait for you
+pub trait InterfaceEventPack: Sized {
+    /// The event's 8-byte interface discriminator
+    const DISCRIMINATOR: [u8; 8];
+    /// Unpacks the event from its data, checking the discriminator
+    fn unpack(data: &[u8]) -> Result<Self, ProgramError>;
+    /// Packs the event, prefixed with its discriminator
+    fn pack<W: std::io::Write>(&self, writer: &mut W) -> Result<(), ProgramError>;
+    /// Emits the event with `sol_log_data`
+    fn emit(&self) -> Result<(), ProgramError> {
+        let mut data = vec![];
+        self.pack(&mut data)?;
+        solana_program::log::sol_log_data(&[&data]);
+        Ok(())
+    }
+}
+
 /// Trait defining a Solana program interface
 pub trait Interface {
     /// The interface's namespace
     const NAMESPACE: &'static str;
+    /// The interface's version
+    const VERSION: u8 = 1;
+    /// Whether this version hashes its discriminators with the
+    /// unversioned namespace, keeping them stable across versions
+    const STABLE_DISCRIMINATORS: bool = false;
+    /// The earlier version of the interface this version is a
+    /// superset of, if any
+    const EXTENDS: Option<u8> = None;
+    /// Whether instructions may declare extension arguments after
+    /// the interface's required arguments
+    const TRAILING_ARGS: TrailingArgsPolicy = TrailingArgsPolicy::Reject;
     /// The instructions required by the interface
     fn instructions() -> Vec<InterfaceInstruction>;
+    /// The accounts expected by one of the interface's
+    /// instructions, in order
+    fn instruction_accounts(_instruction_namespace: &str) -> Vec<InterfaceInstructionAccount> {
+        vec![]
+    }
+    /// The namespaces of any interfaces a program must also
+    /// implement in order to implement this interface
+    fn requires() -> Vec<&'static str> {
+        vec![]
+    }
+    /// The account layouts defined by the interface
+    fn accounts() -> Vec<InterfaceAccount> {
+        vec![]
+    }
+    /// The events defined by the interface
+    fn events() -> Vec<InterfaceEvent> {
+        vec![]
+    }
+    /// The errors defined by the interface
+    fn errors() -> Vec<InterfaceError> {
+        vec![]
+    }
     /// Returns the instructions required by the interface
     /// as a set for evaluation
     fn instruction_set() -> HashSet<InterfaceInstruction> {
@@ -43,79 +127,405 @@ pub trait Interface {
     }
 }
 
+/// Marker trait for a program's instructions that fully implement the
+/// interface `I`, so that client and CPI code can require it at
+/// compile time (ie. `fn route<P: Implements<SRFC20>>()`)
+///
+/// The derive macro `#[derive(SplInterfaceInstruction)]` will
+/// implement this trait for every interface the enum passes evaluation
+/// for without leaving a required instruction unimplemented
+pub trait Implements<I: Interface> {}
+
+/// How an interface treats arguments a program declares after an
+/// instruction's required arguments
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrailingArgsPolicy {
+    /// Instructions must declare exactly the required arguments
+    Reject,
+    /// Instructions may append extension arguments after the required
+    /// arguments (ie. a `memo`), which clients opt into
+    Allow,
+}
+
+/// A type-erased handle to an interface in the registry, for
+/// tooling that needs to walk every interface (ie. client
+/// generators)
+#[derive(Clone, Copy)]
+pub struct RegisteredInterface {
+    /// The interface's namespace
+    pub namespace: &'static str,
+    /// The interface's version
+    pub version: u8,
+    /// The name of the type implementing `Interface` in
+    /// `instructions` (ie. `SRFC20V2`)
+    pub type_name: &'static str,
+    /// Whether this version keeps the unversioned discriminators
+    pub stable_discriminators: bool,
+    /// The earlier version this version is a superset of, if any
+    pub extends: Option<u8>,
+    /// Whether instructions may declare extension arguments
+    pub trailing_args: TrailingArgsPolicy,
+    /// The instructions required by the interface
+    pub instructions: fn() -> Vec<InterfaceInstruction>,
+    /// The accounts expected by one of the interface's instructions
+    pub instruction_accounts: fn(&str) -> Vec<InterfaceInstructionAccount>,
+    /// The namespaces of any interfaces this interface requires
+    pub requires: fn() -> Vec<&'static str>,
+    /// The account layouts defined by the interface
+    pub accounts: fn() -> Vec<InterfaceAccount>,
+    /// The events defined by the interface
+    pub events: fn() -> Vec<InterfaceEvent>,
+    /// The errors defined by the interface
+    pub errors: fn() -> Vec<InterfaceError>,
+}
+impl RegisteredInterface {
+    /// Builds the handle for an `Interface`
+    pub fn of<I: Interface>() -> Self {
+        Self {
+            namespace: I::NAMESPACE,
+            version: I::VERSION,
+            type_name: std::any::type_name::<I>()
+                .rsplit("::")
+                .next()
+                .unwrap_or_default(),
+            stable_discriminators: I::STABLE_DISCRIMINATORS,
+            extends: I::EXTENDS,
+            trailing_args: I::TRAILING_ARGS,
+            instructions: I::instructions,
+            instruction_accounts: I::instruction_accounts,
+            requires: I::requires,
+            accounts: I::accounts,
+            events: I::events,
+            errors: I::errors,
+        }
+    }
+    /// Returns the interface's namespace qualified with its version
+    /// (ie. `srfc20_token@2`), or the bare namespace for version 1
+    pub fn qualified_namespace(&self) -> String {
+        qualified_namespace(self.namespace, self.version)
+    }
+}
+
+/// Qualifies an interface namespace with its version, leaving
+/// version 1 as the bare namespace
+pub fn qualified_namespace(namespace: &str, version: u8) -> String {
+    if version <= 1 {
+        namespace.to_string()
+    } else {
+        format!("{}@{}", namespace, version)
+    }
+}
+
+/// Returns every interface currently accepted into the registry, along
+/// with the example interfaces if the `example-interfaces` feature is
+/// enabled
+pub fn registered_interfaces() -> Vec<RegisteredInterface> {
+    #[allow(unused_mut)]
+    let mut interfaces = vec![
+        RegisteredInterface::of::<SRFC20>(),
+        RegisteredInterface::of::<SRFC21>(),
+        RegisteredInterface::of::<SRFC22>(),
+        RegisteredInterface::of::<SRFC23>(),
+    ];
+    #[cfg(feature = "example-interfaces")]
+    interfaces.extend([
+        RegisteredInterface::of::<SRFC20V2>(),
+        RegisteredInterface::of::<ExampleTokenExtensions>(),
+    ]);
+    interfaces
+}
+
+/// Returns the namespace an interface's discriminators are hashed
+/// under: the versioned namespace, unless the interface's version
+/// keeps stable discriminators
+pub(crate) fn discriminator_domain(interface_namespace: &str, interface_version: u8) -> String {
+    match registered_interface(&qualified_namespace(interface_namespace, interface_version)) {
+        Some(interface) if interface.stable_discriminators => interface.namespace.to_string(),
+        _ => qualified_namespace(interface_namespace, interface_version),
+    }
+}
+
+/// Looks up an interface in the registry by its qualified namespace
+/// (ie. `srfc20_token` or `srfc20_token@2`)
+pub fn registered_interface(qualified_namespace: &str) -> Option<RegisteredInterface> {
+    registered_interfaces()
+        .into_iter()
+        .find(|interface| interface.qualified_namespace() == qualified_namespace)
+}
+
 /// Trait defining a Solana program interface instruction
-#[derive(PartialEq, Eq, Hash)]
+///
+/// Equality and hashing only consider the instruction's signature
+/// (namespaces and arguments), so that a program's declared
+/// instruction matches the interface's definition regardless of
+/// whether the interface marks it as optional or what it returns
+#[derive(Clone, Debug)]
 pub struct InterfaceInstruction {
     /// The interface's namespace
     pub interface_namespace: String,
+    /// The interface's version
+    pub interface_version: u8,
     /// The instruction's namespace
     pub instruction_namespace: String,
     /// The instruction's required arguments
     pub required_args: Vec<RequiredArg>,
+    /// Whether programs may omit the instruction when implementing
+    /// the interface
+    pub optional: bool,
+    /// The types a view instruction returns through `set_return_data`,
+    /// borsh-encoded in order, or empty if it returns nothing
+    pub returns: Vec<RequiredArgType>,
+}
+impl PartialEq for InterfaceInstruction {
+    fn eq(&self, other: &Self) -> bool {
+        self.interface_namespace == other.interface_namespace
+            && self.interface_version == other.interface_version
+            && self.instruction_namespace == other.instruction_namespace
+            && self.required_args == other.required_args
+    }
+}
+impl Eq for InterfaceInstruction {}
+impl Hash for InterfaceInstruction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.interface_namespace.hash(state);
+        self.interface_version.hash(state);
+        self.instruction_namespace.hash(state);
+        self.required_args.hash(state);
+    }
 }
 impl InterfaceInstruction {
     /// Returns the 8-byte discriminator for the instruction
     pub fn discriminator(&self) -> [u8; 8] {
         let mut disc = [0u8; 8];
         disc.copy_from_slice(
-            &solana_program::hash::hash(
-                (self.interface_namespace.to_string() + ":" + &self.instruction_namespace)
-                    .as_bytes(),
-            )
-            .to_bytes()[..8],
+            &solana_program::hash::hash(self.discriminator_preimage().as_bytes()).to_bytes()[..8],
         );
         disc
     }
+    /// Returns the string hashed to build the discriminator, which
+    /// is the versioned namespace unless the interface's version
+    /// keeps stable discriminators
+    pub fn discriminator_preimage(&self) -> String {
+        discriminator_domain(&self.interface_namespace, self.interface_version)
+            + ":"
+            + &self.instruction_namespace
+    }
+    /// Returns the interface's namespace qualified with its version
+    pub fn qualified_interface_namespace(&self) -> String {
+        qualified_namespace(&self.interface_namespace, self.interface_version)
+    }
+    /// Whether the instruction is a read-only query returning data
+    pub fn is_view(&self) -> bool {
+        !self.returns.is_empty()
+    }
+    /// Whether the instruction belongs to the provided `Interface`
+    /// and version
+    pub fn belongs_to<I: Interface>(&self) -> bool {
+        self.interface_namespace == I::NAMESPACE && self.interface_version == I::VERSION
+    }
     /// Converts an instruction namespace and `&ItemFn` to an
     /// `InterfaceInstruction` for evaluation (Anchor)
     pub fn from_item_fn(
         interface_namespace: &String,
+        interface_version: u8,
         instruction_namespace: &String,
         function: &ItemFn,
-    ) -> Self {
+    ) -> syn::Result<Self> {
         let mut required_args = vec![];
         for arg in &function.sig.inputs {
             if let syn::FnArg::Typed(pat_type) = arg {
+                // Anchor's `Context<..>` carries the instruction's
+                // accounts rather than its data
+                if is_anchor_context(&pat_type.ty) {
+                    continue;
+                }
                 if let syn::Pat::Ident(ident) = &*pat_type.pat {
                     required_args.push((
-                        ident.ident.to_string(),
-                        RequiredArgType::from(&*pat_type.ty),
+                        interface_arg_name(&pat_type.attrs)?
+                            .unwrap_or_else(|| ident.ident.to_string()),
+                        required_arg_type(&pat_type.ty)?,
                     ));
                 }
             }
         }
+        Ok(Self {
+            interface_namespace: interface_namespace.to_string(),
+            interface_version,
+            instruction_namespace: instruction_namespace.to_string(),
+            required_args,
+            optional: false,
+            returns: vec![],
+        })
+    }
+    /// Builds the `InterfaceInstruction` for a variant whose payload is a
+    /// single struct (`Variant(Args)`), taking the required arguments
+    /// from the registry since the struct's fields are checked separately
+    /// through `InterfaceArgs`
+    pub fn from_args_struct(
+        interface_namespace: &String,
+        interface_version: u8,
+        instruction_namespace: &String,
+    ) -> Self {
+        let required_args =
+            registered_interface(&qualified_namespace(interface_namespace, interface_version))
+                .and_then(|interface| {
+                    (interface.instructions)()
+                        .into_iter()
+                        .find(|ix| &ix.instruction_namespace == instruction_namespace)
+                })
+                .map(|ix| ix.required_args)
+                .unwrap_or_default();
         Self {
             interface_namespace: interface_namespace.to_string(),
+            interface_version,
             instruction_namespace: instruction_namespace.to_string(),
             required_args,
+            optional: false,
+            returns: vec![],
         }
     }
     /// Converts an instruction namespace and `&Variant` to an
     /// `InterfaceInstruction` for evaluation (Native, Shank)
     pub fn from_variant(
         interface_namespace: &String,
+        interface_version: u8,
         instruction_namespace: &String,
         variant: &Variant,
-    ) -> Self {
+    ) -> syn::Result<Self> {
         let mut required_args = vec![];
         for field in &variant.fields {
-            if let Some(ident) = &field.ident {
-                required_args.push((ident.to_string(), RequiredArgType::from(&field.ty)));
-            }
+            // Unnamed (tuple) fields have an empty name and are
+            // matched by position only
+            let name = match &field.ident {
+                Some(ident) => ident.to_string(),
+                None => String::new(),
+            };
+            required_args.push((
+                interface_arg_name(&field.attrs)?.unwrap_or(name),
+                required_arg_type(&field.ty)?,
+            ));
         }
-        Self {
+        Ok(Self {
             interface_namespace: interface_namespace.to_string(),
+            interface_version,
             instruction_namespace: instruction_namespace.to_string(),
             required_args,
+            optional: false,
+            returns: vec![],
+        })
+    }
+}
+
+/// Returns the interface argument a field or parameter is mapped to
+/// with `#[interface_arg(..)]`, if any, or an error if the annotation
+/// is malformed
+pub(crate) fn interface_arg_name(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("interface_arg"))
+        .map(|attr| attr.parse_args::<Ident>().map(|ident| ident.to_string()))
+        .transpose()
+}
+
+/// An interface instruction a program deliberately does not
+/// support, declared with `#[interface_unimplemented(..)]`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnimplementedInstruction {
+    /// The interface's namespace
+    pub interface_namespace: String,
+    /// The interface's version
+    pub interface_version: u8,
+    /// The instruction's namespace
+    pub instruction_namespace: String,
+}
+impl UnimplementedInstruction {
+    /// Returns the 8-byte discriminator for the instruction
+    pub fn discriminator(&self) -> [u8; 8] {
+        InterfaceInstruction {
+            interface_namespace: self.interface_namespace.to_string(),
+            interface_version: self.interface_version,
+            instruction_namespace: self.instruction_namespace.to_string(),
+            required_args: vec![],
+            optional: false,
+            returns: vec![],
+        }
+        .discriminator()
+    }
+    /// Returns the interface's namespace qualified with its version
+    pub fn qualified_interface_namespace(&self) -> String {
+        qualified_namespace(&self.interface_namespace, self.interface_version)
+    }
+}
+
+/// An interface a program explicitly declares it implements, via
+/// `#[implements(..)]`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ImplementedInterface {
+    /// The interface's namespace
+    pub namespace: String,
+    /// The interface's version
+    pub version: u8,
+}
+impl ImplementedInterface {
+    /// Returns the interface's namespace qualified with its version
+    pub fn qualified_namespace(&self) -> String {
+        qualified_namespace(&self.namespace, self.version)
+    }
+}
+
+/// Returns the 8-byte discriminator for an instruction that does not
+/// implement any interface, hashed under the `global` namespace
+pub fn custom_discriminator(instruction_namespace: &str) -> [u8; 8] {
+    let mut disc = [0u8; 8];
+    disc.copy_from_slice(
+        &solana_program::hash::hash(format!("global:{}", instruction_namespace).as_bytes())
+            .to_bytes()[..8],
+    );
+    disc
+}
+
+/// An account expected by an interface instruction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceInstructionAccount {
+    /// The account's name
+    pub name: &'static str,
+    /// Whether the account must be writable
+    pub is_writable: bool,
+    /// Whether the account must sign the transaction
+    pub is_signer: bool,
+}
+impl InterfaceInstructionAccount {
+    /// A read-only, non-signer account
+    pub const fn readonly(name: &'static str) -> Self {
+        Self {
+            name,
+            is_writable: false,
+            is_signer: false,
+        }
+    }
+    /// A writable, non-signer account
+    pub const fn writable(name: &'static str) -> Self {
+        Self {
+            name,
+            is_writable: true,
+            is_signer: false,
+        }
+    }
+    /// A read-only signer account
+    pub const fn signer(name: &'static str) -> Self {
+        Self {
+            name,
+            is_writable: false,
+            is_signer: true,
         }
     }
 }
 
 /// A required argument for an instruction
-type RequiredArg = (String, RequiredArgType);
+pub type RequiredArg = (String, RequiredArgType);
 
 /// The type of a required argument
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum RequiredArgType {
     /// A `u8` arg
     U8,
@@ -161,111 +571,600 @@ impl From<&RequiredArgType> for Type {
     }
 }
 
-impl From<&Type> for RequiredArgType {
-    fn from(value: &Type) -> Self {
+impl RequiredArgType {
+    /// Converts a type to a `RequiredArgType`, if it is one
+    pub fn try_from_type(value: &Type) -> Option<Self> {
         if value == &parse_quote! { u8 } {
-            RequiredArgType::U8
+            Some(RequiredArgType::U8)
         } else if value == &parse_quote! { u16 } {
-            RequiredArgType::U16
+            Some(RequiredArgType::U16)
         } else if value == &parse_quote! { u32 } {
-            RequiredArgType::U32
+            Some(RequiredArgType::U32)
         } else if value == &parse_quote! { u64 } {
-            RequiredArgType::U64
+            Some(RequiredArgType::U64)
         } else if value == &parse_quote! { u128 } {
-            RequiredArgType::U128
+            Some(RequiredArgType::U128)
         } else if value == &parse_quote! { String } {
-            RequiredArgType::String
-        } else if value == &parse_quote! { solana_program::pubkey::Pubkey } {
-            RequiredArgType::Pubkey
+            Some(RequiredArgType::String)
+        } else if value == &parse_quote! { solana_program::pubkey::Pubkey }
+            || value == &parse_quote! { Pubkey }
+        {
+            Some(RequiredArgType::Pubkey)
         } else {
-            panic!("Invalid type")
+            None
         }
     }
 }
 
-/// Evaluates a program's interface instructions against any declared interfaces
+/// Whether a function argument is Anchor's `Context<..>`
+fn is_anchor_context(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Context"),
+        _ => false,
+    }
+}
+
+/// Converts the type of an instruction argument to a
+/// `RequiredArgType`, or a spanned error if it is not one
+fn required_arg_type(ty: &Type) -> syn::Result<RequiredArgType> {
+    RequiredArgType::try_from_type(ty).ok_or_else(|| {
+        syn::Error::new_spanned(ty, "Unsupported type for an interface instruction argument")
+    })
+}
+
+/// Trait describing the fields of a struct used as an instruction's
+/// payload (`Variant(Args)`), so that they can be checked against the
+/// interface's required arguments at compile time
+///
+/// The derive macro `#[derive(SplInterfaceArgs)]` will implement this
+/// trait for you
+pub trait InterfaceArgs {
+    /// The struct's fields as `(name, type)` pairs, in order
+    const ARGS: &'static [(&'static str, &'static str)];
+}
+
+/// Checks a struct payload's fields against an interface instruction's
+/// required arguments, as `(name, type)` pairs, where unnamed (tuple)
+/// fields only need to match by type
+///
+/// Evaluated in a `const` context by the derive macro, since the
+/// struct's fields aren't visible while expanding the enum
+pub const fn args_match(
+    provided: &[(&str, &str)],
+    required: &[(&str, &str)],
+    allow_trailing: bool,
+) -> bool {
+    if provided.len() < required.len() || (!allow_trailing && provided.len() != required.len()) {
+        return false;
+    }
+    let mut i = 0;
+    while i < required.len() {
+        let name_matches = provided[i].0.is_empty() || str_eq(provided[i].0, required[i].0);
+        if !name_matches || !str_eq(provided[i].1, required[i].1) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// `const` string equality
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Evaluates a program's interface instructions against any declared interfaces,
+/// returning the manifest of implemented interfaces
 pub fn evaluate_interface_instructions(
     declared_instructions: Vec<InterfaceInstruction>,
-) -> Result<(), SplInterfaceError> {
+) -> Result<InterfaceManifest, SplInterfaceError> {
+    evaluate_program_interfaces(declared_instructions, vec![], None)
+}
+
+/// Evaluates a program's interface instructions, along with any interface
+/// instructions it deliberately leaves unimplemented, against any declared
+/// interfaces, returning the manifest of implemented interfaces
+///
+/// If the program explicitly lists the interfaces it implements, every
+/// listed interface must be annotated and every annotation must belong to
+/// a listed interface
+pub fn evaluate_program_interfaces(
+    declared_instructions: Vec<InterfaceInstruction>,
+    unimplemented_instructions: Vec<UnimplementedInstruction>,
+    implemented_interfaces: Option<Vec<ImplementedInterface>>,
+) -> Result<InterfaceManifest, SplInterfaceError> {
     // Initialize a HashMap to keep track of all declared interfaces
     let mut declared_interfaces: HashMap<String, HashSet<InterfaceInstruction>> = HashMap::new();
     // Iterate through all declared instructions and
     // evaluate them against the declared interfaces
     for declared_ix in declared_instructions {
-        if declared_ix.interface_namespace == SRFC20::NAMESPACE {
+        #[cfg(feature = "example-interfaces")]
+        if declared_ix.belongs_to::<SRFC20V2>() {
+            process_declared_instruction::<SRFC20V2>(&mut declared_interfaces, declared_ix)?;
+            continue;
+        }
+        #[cfg(feature = "example-interfaces")]
+        if declared_ix.belongs_to::<ExampleTokenExtensions>() {
+            process_declared_instruction::<ExampleTokenExtensions>(
+                &mut declared_interfaces,
+                declared_ix,
+            )?;
+            continue;
+        }
+        if declared_ix.belongs_to::<SRFC20>() {
             process_declared_instruction::<SRFC20>(&mut declared_interfaces, declared_ix)?
-        } else if declared_ix.interface_namespace == SRFC21::NAMESPACE {
+        } else if declared_ix.belongs_to::<SRFC21>() {
             process_declared_instruction::<SRFC21>(&mut declared_interfaces, declared_ix)?
-        } else if declared_ix.interface_namespace == SRFC22::NAMESPACE {
+        } else if declared_ix.belongs_to::<SRFC22>() {
             process_declared_instruction::<SRFC22>(&mut declared_interfaces, declared_ix)?
-        } else if declared_ix.interface_namespace == SRFC23::NAMESPACE {
+        } else if declared_ix.belongs_to::<SRFC23>() {
             process_declared_instruction::<SRFC23>(&mut declared_interfaces, declared_ix)?
         } else {
-            return Err(SplInterfaceError::InvalidInterfaceNamespace);
+            return Err(unknown_interface_error(&declared_ix.interface_namespace));
         }
     }
-    // Make sure all declared interfaces have no remaining unmatched instructions
+    // Remove any deliberately unimplemented instructions from the evaluation
+    // sets, since they are satisfied by the generated "unsupported" handler
+    for unimplemented_ix in &unimplemented_instructions {
+        process_unimplemented_instruction(&mut declared_interfaces, unimplemented_ix)?
+    }
+    // Make sure the annotated interfaces match the explicitly implemented
+    // interfaces, if provided
+    if let Some(implemented_interfaces) = implemented_interfaces {
+        evaluate_implemented_interfaces(&declared_interfaces, &implemented_interfaces)?;
+    }
+    // Make sure all declared interfaces have no remaining unmatched instructions,
+    // aside from optional ones
     for x in declared_interfaces.values() {
-        if !x.is_empty() {
+        if x.iter().any(|ix| !ix.optional) {
             dump_remaining_interface_instructions(declared_interfaces);
             return Err(SplInterfaceError::InstructionMissing);
         }
     }
+    // Make sure all declared interfaces have their required interfaces
+    // implemented as well
+    evaluate_required_interfaces(&declared_interfaces)?;
+    Ok(build_manifest(
+        &declared_interfaces,
+        &unimplemented_instructions,
+    ))
+}
+
+/// Checks the annotated interfaces against the interfaces a program
+/// explicitly declares it implements, in both directions
+fn evaluate_implemented_interfaces(
+    declared_interfaces: &HashMap<String, HashSet<InterfaceInstruction>>,
+    implemented_interfaces: &[ImplementedInterface],
+) -> Result<(), SplInterfaceError> {
+    let mut implemented = HashSet::new();
+    for interface in implemented_interfaces {
+        if registered_interface(&interface.qualified_namespace()).is_none() {
+            return Err(unknown_interface_error(&interface.namespace));
+        }
+        implemented.insert(interface.qualified_namespace());
+    }
+    let mut not_implemented: Vec<&String> = implemented
+        .iter()
+        .filter(|namespace| !declared_interfaces.contains_key(*namespace))
+        .collect();
+    if !not_implemented.is_empty() {
+        not_implemented.sort();
+        println!("\n\nThe following declared interfaces have no annotated instructions:\n");
+        for namespace in not_implemented {
+            println!("   - {}", namespace);
+        }
+        println!("\n");
+        return Err(SplInterfaceError::DeclaredInterfaceNotImplemented);
+    }
+    let mut undeclared: Vec<&String> = declared_interfaces
+        .keys()
+        .filter(|namespace| !implemented.contains(*namespace))
+        .collect();
+    if !undeclared.is_empty() {
+        undeclared.sort();
+        println!("\n\nThe following annotated interfaces are missing from `implements`:\n");
+        for namespace in undeclared {
+            println!("   - {}", namespace);
+        }
+        println!("\n");
+        return Err(SplInterfaceError::UndeclaredInterface);
+    }
     Ok(())
 }
 
-/// Processed a declared instruction by checking to see if it exists in the `HashMap`
-/// and if it does, removing the instruction from the `HashSet`
-fn process_declared_instruction<I: Interface>(
+/// Returns the error for an interface that isn't in the registry,
+/// distinguishing an unknown version from an unknown namespace
+pub(crate) fn unknown_interface_error(interface_namespace: &str) -> SplInterfaceError {
+    if registered_interfaces()
+        .iter()
+        .any(|i| i.namespace == interface_namespace)
+    {
+        SplInterfaceError::InvalidInterfaceVersion
+    } else {
+        SplInterfaceError::InvalidInterfaceNamespace
+    }
+}
+
+/// Processes an unimplemented instruction by removing it from its
+/// interface's evaluation set, which is created if none of the
+/// interface's instructions were implemented
+fn process_unimplemented_instruction(
     declared_interfaces: &mut HashMap<String, HashSet<InterfaceInstruction>>,
-    declared_ix: InterfaceInstruction,
+    unimplemented_ix: &UnimplementedInstruction,
 ) -> Result<(), SplInterfaceError> {
-    match declared_interfaces.get_mut(&declared_ix.interface_namespace) {
-        Some(set) => {
-            if !set.remove(&declared_ix) {
-                return Err(SplInterfaceError::InstructionNotFound);
-            }
+    let interface = registered_interface(&unimplemented_ix.qualified_interface_namespace())
+        .ok_or_else(|| unknown_interface_error(&unimplemented_ix.interface_namespace))?;
+    let set = declared_interfaces
+        .entry(interface.qualified_namespace())
+        .or_insert_with(|| (interface.instructions)().into_iter().collect());
+    match set
+        .iter()
+        .find(|ix| ix.instruction_namespace == unimplemented_ix.instruction_namespace)
+        .cloned()
+    {
+        Some(ix) => {
+            set.remove(&ix);
+            Ok(())
         }
         None => {
-            let mut set = I::instruction_set();
-            if !set.remove(&declared_ix) {
-                match set
+            if (interface.instructions)()
+                .iter()
+                .any(|ix| ix.instruction_namespace == unimplemented_ix.instruction_namespace)
+            {
+                println!("\n\nThe following interface instructions are both implemented and declared as unimplemented:\n");
+                println!(
+                    "  - {}::{}",
+                    unimplemented_ix.qualified_interface_namespace(),
+                    unimplemented_ix.instruction_namespace
+                );
+                Err(SplInterfaceError::ConflictingUnimplementedInstruction)
+            } else {
+                println!("\n\nFound the following unknown interface instructions:\n");
+                println!(
+                    "  - {}::{}",
+                    unimplemented_ix.qualified_interface_namespace(),
+                    unimplemented_ix.instruction_namespace
+                );
+                Err(SplInterfaceError::InstructionNotFound)
+            }
+        }
+    }
+}
+
+/// Builds the manifest of implemented interfaces from the evaluation
+/// sets, where any instruction remaining in a set was omitted
+///
+/// A version that extends an earlier version with stable
+/// discriminators also claims compliance with the earlier version
+fn build_manifest(
+    declared_interfaces: &HashMap<String, HashSet<InterfaceInstruction>>,
+    unimplemented_instructions: &[UnimplementedInstruction],
+) -> InterfaceManifest {
+    let mut interfaces = vec![];
+    for (qualified, remaining) in declared_interfaces {
+        if let Some(interface) = registered_interface(qualified) {
+            let unsupported: Vec<&str> = unimplemented_instructions
+                .iter()
+                .filter(|ix| &ix.qualified_interface_namespace() == qualified)
+                .map(|ix| ix.instruction_namespace.as_str())
+                .collect();
+            interfaces.push(manifest_interface(&interface, remaining, &unsupported));
+            if let Some(base) = interface
+                .extends
+                .filter(|_| interface.stable_discriminators)
+                .and_then(|version| {
+                    registered_interface(&qualified_namespace(interface.namespace, version))
+                })
+            {
+                if !declared_interfaces.contains_key(&base.qualified_namespace()) {
+                    interfaces.push(manifest_interface(&base, remaining, &unsupported));
+                }
+            }
+        }
+    }
+    interfaces.sort_by(|a, b| (&a.namespace, a.version).cmp(&(&b.namespace, b.version)));
+    InterfaceManifest { interfaces }
+}
+
+/// Builds the manifest entry for an interface, where any instruction
+/// remaining in the evaluation set was omitted
+fn manifest_interface(
+    interface: &RegisteredInterface,
+    remaining: &HashSet<InterfaceInstruction>,
+    unsupported: &[&str],
+) -> ManifestInterface {
+    ManifestInterface {
+        namespace: interface.namespace.to_string(),
+        version: interface.version,
+        instructions: (interface.instructions)()
+            .into_iter()
+            .map(|ix| ManifestInstruction {
+                namespace: ix.instruction_namespace.to_string(),
+                discriminator: ix.discriminator(),
+                optional: ix.optional,
+                status: if unsupported.contains(&ix.instruction_namespace.as_str()) {
+                    ManifestInstructionStatus::Unsupported
+                } else if remaining
                     .iter()
-                    .find(|i| &i.instruction_namespace == &declared_ix.instruction_namespace)
+                    .any(|r| r.instruction_namespace == ix.instruction_namespace)
                 {
-                    Some(ins) => {
-                        println!(
-                            "\n\nIncorrect arguments for interface instruction `{}::{}`:\n",
-                            declared_ix.interface_namespace, declared_ix.instruction_namespace
-                        );
-                        println!("Provided arguments:");
-                        for arg in &declared_ix.required_args {
-                            println!("  - {}: {}", arg.0, arg.1);
-                        }
-                        println!("\n");
-                        println!("Required arguments:");
-                        for arg in &ins.required_args {
-                            println!("  - {}: {}", arg.0, arg.1);
-                        }
-                        println!("\n");
-                        return Err(SplInterfaceError::MissingArgument);
-                    }
-                    None => {
-                        println!("\n\nFound the following unknown interface instructions:\n");
-                        println!(
-                            "  - {}::{}",
-                            declared_ix.interface_namespace, declared_ix.instruction_namespace
-                        );
-                        return Err(SplInterfaceError::InstructionNotFound);
+                    ManifestInstructionStatus::Omitted
+                } else {
+                    ManifestInstructionStatus::Implemented
+                },
+            })
+            .collect(),
+    }
+}
+
+/// Checks that every interface a declared interface requires has
+/// also been declared
+fn evaluate_required_interfaces(
+    declared_interfaces: &HashMap<String, HashSet<InterfaceInstruction>>,
+) -> Result<(), SplInterfaceError> {
+    let mut missing: Vec<(&String, RegisteredInterface)> = vec![];
+    for namespace in declared_interfaces.keys() {
+        if let Some(interface) = registered_interface(namespace) {
+            for required in (interface.requires)() {
+                if !declared_interfaces
+                    .keys()
+                    .any(|declared| declared.split('@').next() == Some(required))
+                {
+                    if let Some(parent) = registered_interface(required) {
+                        missing.push((namespace, parent));
                     }
                 }
             }
-            declared_interfaces.insert(declared_ix.interface_namespace, set);
         }
     }
+    if missing.is_empty() {
+        return Ok(());
+    }
+    println!("\n\nThe following required interface instructions were not implemented:\n");
+    for (namespace, parent) in missing {
+        for ix in (parent.instructions)() {
+            println!(
+                "   - {}::{} (required by `{}`)",
+                parent.namespace, ix.instruction_namespace, namespace
+            );
+        }
+    }
+    println!("\n");
+    Err(SplInterfaceError::RequiredInterfaceMissing)
+}
+
+/// Processes a declared instruction by finding its interface instruction
+/// in the evaluation set (created on first use), comparing their arguments
+/// under the interface's trailing arguments policy, and removing the
+/// instruction from the `HashSet`
+fn process_declared_instruction<I: Interface>(
+    declared_interfaces: &mut HashMap<String, HashSet<InterfaceInstruction>>,
+    declared_ix: InterfaceInstruction,
+) -> Result<(), SplInterfaceError> {
+    let set = declared_interfaces
+        .entry(declared_ix.qualified_interface_namespace())
+        .or_insert_with(I::instruction_set);
+    let interface_ix = match set
+        .iter()
+        .find(|i| i.instruction_namespace == declared_ix.instruction_namespace)
+        .cloned()
+    {
+        Some(interface_ix) => interface_ix,
+        None => {
+            if I::instructions()
+                .iter()
+                .any(|i| i.instruction_namespace == declared_ix.instruction_namespace)
+            {
+                println!(
+                    "\n\nThe following interface instructions were implemented more than once:\n"
+                );
+            } else {
+                println!("\n\nFound the following unknown interface instructions:\n");
+            }
+            println!(
+                "  - {}::{}",
+                declared_ix.qualified_interface_namespace(),
+                declared_ix.instruction_namespace
+            );
+            return Err(SplInterfaceError::InstructionNotFound);
+        }
+    };
+    let mismatches = compare_args(
+        &interface_ix.required_args,
+        &declared_ix.required_args,
+        I::TRAILING_ARGS,
+    );
+    if !mismatches.is_empty() {
+        println!(
+            "\n\nIncorrect arguments for interface instruction `{}::{}`:\n",
+            declared_ix.qualified_interface_namespace(),
+            declared_ix.instruction_namespace
+        );
+        print_arg_mismatches(
+            "arguments",
+            &declared_ix.required_args,
+            &interface_ix.required_args,
+            &mismatches,
+        );
+        return Err(SplInterfaceError::MissingArgument);
+    }
+    set.remove(&interface_ix);
     Ok(())
 }
 
+/// A difference between an interface instruction's required arguments
+/// and a program's declared arguments
+pub(crate) enum ArgMismatch<'a> {
+    /// The declared argument has a different name or type
+    Mismatched {
+        position: usize,
+        expected: &'a RequiredArg,
+        found: &'a RequiredArg,
+    },
+    /// The required argument wasn't declared
+    Missing {
+        position: usize,
+        expected: &'a RequiredArg,
+    },
+    /// The declared argument comes after the required arguments, which
+    /// the interface doesn't allow
+    Unexpected {
+        position: usize,
+        found: &'a RequiredArg,
+    },
+}
+
+impl std::fmt::Display for ArgMismatch<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgMismatch::Mismatched {
+                position,
+                expected,
+                found,
+            } => write!(
+                f,
+                "position {}: expected `{}: {}`, found `{}: {}`",
+                position,
+                expected.0,
+                expected.1,
+                arg_name(found),
+                found.1
+            ),
+            ArgMismatch::Missing { position, expected } => write!(
+                f,
+                "position {}: expected `{}: {}`, found nothing",
+                position, expected.0, expected.1
+            ),
+            ArgMismatch::Unexpected { position, found } => write!(
+                f,
+                "position {}: unexpected trailing argument `{}: {}` (the interface does not allow extension arguments)",
+                position,
+                arg_name(found),
+                found.1
+            ),
+        }
+    }
+}
+
+/// Prints the provided and required arguments (or fields) along with
+/// their differences for error reporting
+pub(crate) fn print_arg_mismatches(
+    kind: &str,
+    provided: &[RequiredArg],
+    required: &[RequiredArg],
+    mismatches: &[ArgMismatch],
+) {
+    println!("Provided {}:", kind);
+    for arg in provided {
+        println!("  - {}: {}", arg_name(arg), arg.1);
+    }
+    println!("\n");
+    println!("Required {}:", kind);
+    for arg in required {
+        println!("  - {}: {}", arg_name(arg), arg.1);
+    }
+    println!("\n");
+    println!("Differences:");
+    for mismatch in mismatches {
+        println!("  - {}", mismatch);
+    }
+    println!("\n");
+}
+
+/// Converts a `snake_case` namespace to `PascalCase`
+pub fn to_pascal_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Converts a `snake_case` namespace to `camelCase`
+pub fn to_camel_case(snake: &str) -> String {
+    let pascal = to_pascal_case(snake);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Converts a `PascalCase` identifier to `snake_case`
+pub fn to_snake_case(ident: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in ident.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Returns an argument's name for error reporting, using `_` for
+/// unnamed (tuple) fields
+fn arg_name(arg: &RequiredArg) -> &str {
+    if arg.0.is_empty() {
+        "_"
+    } else {
+        &arg.0
+    }
+}
+
+/// Compares a program's declared arguments to an interface instruction's
+/// required arguments position by position
+pub(crate) fn compare_args<'a>(
+    required: &'a [RequiredArg],
+    provided: &'a [RequiredArg],
+    trailing_args: TrailingArgsPolicy,
+) -> Vec<ArgMismatch<'a>> {
+    let mut mismatches = vec![];
+    for (position, expected) in required.iter().enumerate() {
+        match provided.get(position) {
+            Some(found)
+                if (found.0.is_empty() || found.0 == expected.0) && found.1 == expected.1 => {}
+            Some(found) => mismatches.push(ArgMismatch::Mismatched {
+                position,
+                expected,
+                found,
+            }),
+            None => mismatches.push(ArgMismatch::Missing { position, expected }),
+        }
+    }
+    if trailing_args == TrailingArgsPolicy::Reject {
+        for (position, found) in provided.iter().enumerate().skip(required.len()) {
+            mismatches.push(ArgMismatch::Unexpected { position, found });
+        }
+    }
+    mismatches
+}
+
 /// Dumps any remaining interface instructions in the evaluation
 /// set for error reporting
 fn dump_remaining_interface_instructions(
@@ -273,7 +1172,7 @@ fn dump_remaining_interface_instructions(
 ) {
     println!("\n\nThe following interface instructions were not implemented:\n");
     for (namespace, set) in declared_interfaces {
-        for ix in set {
+        for ix in set.iter().filter(|ix| !ix.optional) {
             println!("   - {}::{}", namespace, ix.instruction_namespace);
         }
     }
@@ -0,0 +1,264 @@
+//! The discriminator lockfile: a checked-in record of the wire format
+//! of a program's instruction enums, so that accidental ABI breaks
+//! fail the build instead of clients
+//!
+//! When `interfaces.lock` exists at the root of a crate, the derive
+//! compares every instruction enum (and args struct) it expands
+//! against the lock, one line per variant:
+//!
+//! ```text
+//! TokenInstruction::Transfer 1d8ae8b7a6f23a1c { amount: u64 }
+//! TokenInstruction::Legacy 2b5c0e8d9f1a3c47 tag=4 (u8, Pubkey)
+//! TransferArgs { amount: u64, memo: Option<String> }
+//! ```
+//!
+//! A variant lists the discriminator written by `pack` first, followed
+//! by any others `unpack` accepts and, with legacy discriminators, its
+//! borsh variant tag. Types are written without their module path, so
+//! `Pubkey` and `solana_program::pubkey::Pubkey` lock the same layout.
+//!
+//! Only breaking changes fail the build: added variants are reported,
+//! but the lock is only rewritten when the build runs with
+//! `SPL_INTERFACE_UPDATE_LOCK=1`. Entries are keyed by the item's name,
+//! so a crate can't lock two items with the same name
+
+use crate::error::SplInterfaceError;
+use std::fmt;
+
+/// The name of the lockfile, at the root of a crate
+pub const LOCKFILE_NAME: &str = "interfaces.lock";
+/// The environment variable allowing the derive to rewrite the lock
+pub const UPDATE_LOCK_ENV: &str = "SPL_INTERFACE_UPDATE_LOCK";
+
+const LOCKFILE_HEADER: &str = "\
+# Wire format of this crate's interface instructions.
+# Do not edit. Regenerate with `SPL_INTERFACE_UPDATE_LOCK=1 cargo build`
+# after an intended breaking change.
+";
+
+/// The locked wire format of an instruction enum variant or an args
+/// struct
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockEntry {
+    /// The instruction enum or args struct
+    pub item: String,
+    /// The variant, for instruction enums
+    pub variant: Option<String>,
+    /// The discriminator written by `pack` first, then any others
+    /// accepted by `unpack`
+    pub discriminators: Vec<[u8; 8]>,
+    /// The borsh variant tag, if the enum accepts legacy discriminators
+    pub legacy_tag: Option<u8>,
+    /// The fields in declaration order (ie. `{ amount: u64 }`)
+    pub layout: String,
+}
+
+impl LockEntry {
+    /// Returns the entry's key: `Item::Variant` or `Item`
+    pub fn key(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!("{}::{}", self.item, variant),
+            None => self.item.clone(),
+        }
+    }
+
+    /// Parses an entry from its line in the lockfile
+    fn parse(line: &str) -> Option<Self> {
+        let (head, layout) = match line.find(['{', '(']) {
+            Some(index) => (&line[..index], line[index..].trim()),
+            None => (line, ""),
+        };
+        let mut tokens = head.split_whitespace();
+        let key = tokens.next()?;
+        let (item, variant) = match key.split_once("::") {
+            Some((item, variant)) => (item.to_string(), Some(variant.to_string())),
+            None => (key.to_string(), None),
+        };
+        let mut discriminators = vec![];
+        let mut legacy_tag = None;
+        for token in tokens {
+            match token.strip_prefix("tag=") {
+                Some(tag) => legacy_tag = Some(tag.parse().ok()?),
+                None => discriminators.push(parse_discriminator(token)?),
+            }
+        }
+        Some(Self {
+            item,
+            variant,
+            discriminators,
+            legacy_tag,
+            layout: normalize_layout(layout),
+        })
+    }
+}
+
+/// Strips the module paths from the types of a layout
+/// (ie. `Option<solana_program::pubkey::Pubkey>` to `Option<Pubkey>`),
+/// which don't change its wire format
+pub fn normalize_layout(layout: &str) -> String {
+    let mut normalized = String::new();
+    let mut rest = layout;
+    while let Some(index) = rest.find("::") {
+        let segment = rest[..index]
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map_or(0, |position| position + 1);
+        normalized.push_str(&rest[..segment]);
+        rest = &rest[index + 2..];
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+impl fmt::Display for LockEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())?;
+        for discriminator in &self.discriminators {
+            write!(f, " ")?;
+            for byte in discriminator {
+                write!(f, "{:02x}", byte)?;
+            }
+        }
+        if let Some(tag) = self.legacy_tag {
+            write!(f, " tag={}", tag)?;
+        }
+        if !self.layout.is_empty() {
+            write!(f, " {}", self.layout)?;
+        }
+        Ok(())
+    }
+}
+
+/// A difference between the lock and the current wire format
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LockChange {
+    /// The entry isn't in the lock yet
+    Added(LockEntry),
+    /// The locked entry no longer exists
+    Removed(LockEntry),
+    /// The entry's discriminators or layout changed
+    Changed {
+        locked: LockEntry,
+        current: LockEntry,
+    },
+}
+
+impl LockChange {
+    /// Whether the change breaks clients of the locked wire format
+    pub fn is_breaking(&self) -> bool {
+        !matches!(self, Self::Added(_))
+    }
+}
+
+impl fmt::Display for LockChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added(entry) => write!(f, "`{}` added: {}", entry.key(), entry),
+            Self::Removed(entry) => write!(f, "`{}` was removed: {}", entry.key(), entry),
+            Self::Changed { locked, current } => write!(
+                f,
+                "`{}` changed:\n    locked:  {}\n    current: {}",
+                locked.key(),
+                locked,
+                current
+            ),
+        }
+    }
+}
+
+/// Parses the entries of a lockfile, skipping comments and blank lines
+pub fn parse_lockfile(data: &str) -> Result<Vec<LockEntry>, SplInterfaceError> {
+    data.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            LockEntry::parse(line).ok_or_else(|| {
+                println!("Malformed `{}` entry: {}", LOCKFILE_NAME, line);
+                SplInterfaceError::LockfileMalformed
+            })
+        })
+        .collect()
+}
+
+/// Renders the entries of a lockfile
+pub fn render_lockfile(entries: &[LockEntry]) -> String {
+    let mut data = LOCKFILE_HEADER.to_string();
+    for entry in entries {
+        data.push_str(&entry.to_string());
+        data.push('\n');
+    }
+    data
+}
+
+/// Compares the locked entries of `item` against its current ones
+pub fn diff_lock_entries(
+    locked: &[LockEntry],
+    item: &str,
+    current: &[LockEntry],
+) -> Vec<LockChange> {
+    let locked: Vec<&LockEntry> = locked.iter().filter(|entry| entry.item == item).collect();
+    let mut changes = vec![];
+    for entry in current {
+        match locked.iter().find(|locked| locked.key() == entry.key()) {
+            None => changes.push(LockChange::Added(entry.clone())),
+            Some(locked) if *locked != entry => changes.push(LockChange::Changed {
+                locked: (*locked).clone(),
+                current: entry.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for entry in locked {
+        if !current.iter().any(|current| current.key() == entry.key()) {
+            changes.push(LockChange::Removed(entry.clone()));
+        }
+    }
+    changes
+}
+
+/// Compares every item of two lockfiles, ie. those of a program's
+/// current build and of its upgrade
+pub fn diff_lockfiles(old: &[LockEntry], new: &[LockEntry]) -> Vec<LockChange> {
+    let mut items: Vec<&str> = vec![];
+    for entry in old.iter().chain(new) {
+        if !items.contains(&entry.item.as_str()) {
+            items.push(&entry.item);
+        }
+    }
+    items
+        .into_iter()
+        .flat_map(|item| {
+            let current: Vec<LockEntry> = new.iter().filter(|e| e.item == item).cloned().collect();
+            diff_lock_entries(old, item, &current)
+        })
+        .collect()
+}
+
+/// Replaces the locked entries of `item` with its current ones, in
+/// place of the first of them
+pub fn update_lock_entries(
+    locked: Vec<LockEntry>,
+    item: &str,
+    current: Vec<LockEntry>,
+) -> Vec<LockEntry> {
+    let position = locked
+        .iter()
+        .position(|entry| entry.item == item)
+        .unwrap_or(locked.len());
+    let mut entries: Vec<LockEntry> = locked
+        .into_iter()
+        .filter(|entry| entry.item != item)
+        .collect();
+    entries.splice(position..position, current);
+    entries
+}
+
+fn parse_discriminator(token: &str) -> Option<[u8; 8]> {
+    if token.len() != 16 {
+        return None;
+    }
+    let mut discriminator = [0u8; 8];
+    for (i, byte) in discriminator.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(token.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(discriminator)
+}
@@ -0,0 +1,299 @@
+//! The interface manifest: a summary of every interface a program
+//! implements, produced when its interface instructions are
+//! evaluated
+
+/// The manifest of interfaces implemented by a program
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InterfaceManifest {
+    /// The implemented interfaces, sorted by namespace and version
+    pub interfaces: Vec<ManifestInterface>,
+}
+
+/// An interface listed in a program's manifest
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestInterface {
+    /// The interface's namespace
+    pub namespace: String,
+    /// The interface's version
+    pub version: u8,
+    /// Every instruction defined by the interface, in registry order
+    pub instructions: Vec<ManifestInstruction>,
+}
+
+/// An interface instruction listed in a program's manifest
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestInstruction {
+    /// The instruction's namespace
+    pub namespace: String,
+    /// The instruction's 8-byte discriminator
+    pub discriminator: [u8; 8],
+    /// Whether the interface marks the instruction as optional
+    pub optional: bool,
+    /// Whether the program implements the instruction
+    pub status: ManifestInstructionStatus,
+}
+
+/// The implementation status of an instruction in a manifest
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ManifestInstructionStatus {
+    /// The program implements the instruction
+    Implemented,
+    /// The program omits the instruction (optional instructions only)
+    Omitted,
+    /// The program deliberately does not support the instruction and
+    /// rejects it with `UnsupportedInterfaceInstruction`
+    Unsupported,
+}
+
+impl InterfaceManifest {
+    /// Looks up an interface in the manifest by its namespace
+    /// and version
+    pub fn interface(&self, namespace: &str, version: u8) -> Option<&ManifestInterface> {
+        self.interfaces
+            .iter()
+            .find(|i| i.namespace == namespace && i.version == version)
+    }
+    /// Returns every version of an interface claimed by the manifest,
+    /// so that clients can negotiate the newest one they support
+    pub fn versions(&self, namespace: &str) -> Vec<u8> {
+        self.interfaces
+            .iter()
+            .filter(|i| i.namespace == namespace)
+            .map(|i| i.version)
+            .collect()
+    }
+    /// Merges another manifest into this one, ie. when a binary embeds
+    /// one per instruction enum
+    ///
+    /// An instruction is implemented if either manifest implements it
+    pub fn merge(&mut self, other: InterfaceManifest) {
+        for interface in other.interfaces {
+            match self
+                .interfaces
+                .iter_mut()
+                .find(|i| i.namespace == interface.namespace && i.version == interface.version)
+            {
+                Some(existing) => {
+                    for instruction in interface.instructions {
+                        match existing
+                            .instructions
+                            .iter_mut()
+                            .find(|ix| ix.namespace == instruction.namespace)
+                        {
+                            Some(existing_ix) if instruction.is_implemented() => {
+                                *existing_ix = instruction
+                            }
+                            Some(_) => {}
+                            None => existing.instructions.push(instruction),
+                        }
+                    }
+                }
+                None => self.interfaces.push(interface),
+            }
+        }
+    }
+}
+
+impl ManifestInterface {
+    /// Looks up an instruction in the manifest entry by its namespace
+    pub fn instruction(&self, namespace: &str) -> Option<&ManifestInstruction> {
+        self.instructions.iter().find(|i| i.namespace == namespace)
+    }
+    /// Whether the program implements every required instruction of
+    /// the interface, rather than rejecting some as unsupported
+    pub fn is_fully_implemented(&self) -> bool {
+        self.instructions
+            .iter()
+            .all(|i| i.optional || i.status == ManifestInstructionStatus::Implemented)
+    }
+    /// Returns the interface's namespace qualified with its version
+    pub fn qualified_namespace(&self) -> String {
+        crate::qualified_namespace(&self.namespace, self.version)
+    }
+}
+
+/// A difference between the manifests of two builds of a program,
+/// classified by whether it breaks clients of the old build
+///
+/// An interface instruction's arguments are fixed by the interface's
+/// version, so changing them surfaces as a change of version
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ManifestChange {
+    /// The program implements a new interface
+    InterfaceAdded { interface: String },
+    /// The program no longer implements an interface, unless another
+    /// version of it keeps every implemented instruction's
+    /// discriminator (ie. a superset with stable discriminators)
+    InterfaceRemoved {
+        interface: String,
+        superseded_by: Option<String>,
+    },
+    /// The program implements an instruction it didn't before
+    InstructionAdded {
+        interface: String,
+        instruction: String,
+    },
+    /// The program no longer implements an instruction
+    InstructionRemoved {
+        interface: String,
+        instruction: String,
+    },
+    /// An implemented instruction's discriminator changed
+    DiscriminatorChanged {
+        interface: String,
+        instruction: String,
+        old: [u8; 8],
+        new: [u8; 8],
+    },
+}
+
+impl ManifestChange {
+    /// Whether the change breaks clients of the old build
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            Self::InterfaceAdded { .. } | Self::InstructionAdded { .. } => false,
+            Self::InterfaceRemoved { superseded_by, .. } => superseded_by.is_none(),
+            Self::InstructionRemoved { .. } | Self::DiscriminatorChanged { .. } => true,
+        }
+    }
+}
+
+impl std::fmt::Display for ManifestChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InterfaceAdded { interface } => write!(f, "`{}` added", interface),
+            Self::InterfaceRemoved {
+                interface,
+                superseded_by: None,
+            } => write!(f, "`{}` removed", interface),
+            Self::InterfaceRemoved {
+                interface,
+                superseded_by: Some(superseded_by),
+            } => write!(f, "`{}` superseded by `{}`", interface, superseded_by),
+            Self::InstructionAdded {
+                interface,
+                instruction,
+            } => write!(f, "`{}::{}` added", interface, instruction),
+            Self::InstructionRemoved {
+                interface,
+                instruction,
+            } => write!(f, "`{}::{}` removed", interface, instruction),
+            Self::DiscriminatorChanged {
+                interface,
+                instruction,
+                old,
+                new,
+            } => write!(
+                f,
+                "`{}::{}` discriminator changed from {} to {}",
+                interface,
+                instruction,
+                hex(old),
+                hex(new)
+            ),
+        }
+    }
+}
+
+impl ManifestInstruction {
+    /// Whether the program handles the instruction
+    fn is_implemented(&self) -> bool {
+        self.status == ManifestInstructionStatus::Implemented
+    }
+}
+
+/// Compares the manifest of a program's current build (`old`) against
+/// the manifest of its upgrade (`new`)
+pub fn diff_manifests(old: &InterfaceManifest, new: &InterfaceManifest) -> Vec<ManifestChange> {
+    let mut changes = vec![];
+    for old_interface in &old.interfaces {
+        let interface = old_interface.qualified_namespace();
+        let new_interface = match new.interface(&old_interface.namespace, old_interface.version) {
+            Some(new_interface) => new_interface,
+            None => {
+                let superseded_by = new
+                    .interfaces
+                    .iter()
+                    .filter(|i| i.namespace == old_interface.namespace)
+                    .find(|i| keeps_implemented_instructions(old_interface, i))
+                    .map(ManifestInterface::qualified_namespace);
+                changes.push(ManifestChange::InterfaceRemoved {
+                    interface,
+                    superseded_by,
+                });
+                continue;
+            }
+        };
+        for old_ix in old_interface
+            .instructions
+            .iter()
+            .filter(|ix| ix.is_implemented())
+        {
+            let instruction = old_ix.namespace.clone();
+            match new_interface.instruction(&old_ix.namespace) {
+                Some(new_ix) if !new_ix.is_implemented() => {
+                    changes.push(ManifestChange::InstructionRemoved {
+                        interface: interface.clone(),
+                        instruction,
+                    })
+                }
+                Some(new_ix) if new_ix.discriminator != old_ix.discriminator => {
+                    changes.push(ManifestChange::DiscriminatorChanged {
+                        interface: interface.clone(),
+                        instruction,
+                        old: old_ix.discriminator,
+                        new: new_ix.discriminator,
+                    })
+                }
+                Some(_) => {}
+                None => changes.push(ManifestChange::InstructionRemoved {
+                    interface: interface.clone(),
+                    instruction,
+                }),
+            }
+        }
+        for new_ix in new_interface
+            .instructions
+            .iter()
+            .filter(|ix| ix.is_implemented())
+        {
+            if !old_interface
+                .instruction(&new_ix.namespace)
+                .is_some_and(ManifestInstruction::is_implemented)
+            {
+                changes.push(ManifestChange::InstructionAdded {
+                    interface: interface.clone(),
+                    instruction: new_ix.namespace.clone(),
+                });
+            }
+        }
+    }
+    for new_interface in &new.interfaces {
+        if old
+            .interface(&new_interface.namespace, new_interface.version)
+            .is_none()
+        {
+            changes.push(ManifestChange::InterfaceAdded {
+                interface: new_interface.qualified_namespace(),
+            });
+        }
+    }
+    changes
+}
+
+/// Whether `new` implements every instruction `old` implements, with
+/// the same discriminator
+fn keeps_implemented_instructions(old: &ManifestInterface, new: &ManifestInterface) -> bool {
+    old.instructions
+        .iter()
+        .filter(|ix| ix.is_implemented())
+        .all(|old_ix| {
+            new.instruction(&old_ix.namespace).is_some_and(|new_ix| {
+                new_ix.is_implemented() && new_ix.discriminator == old_ix.discriminator
+            })
+        })
+}
+
+fn hex(discriminator: &[u8; 8]) -> String {
+    discriminator.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,20 @@
+//! View instructions: read-only interface instructions that hand their
+//! output back to the caller through `set_return_data`, borsh-encoded
+
+use borsh::BorshDeserialize;
+use solana_program::pubkey::Pubkey;
+
+/// Decodes the return data of a view instruction, as reported by
+/// `get_return_data` or a transaction simulation
+///
+/// Returns `None` if no data was returned, if it was returned by a
+/// program other than `program_id` or if it doesn't decode as `T`
+pub fn decode_return_data<T: BorshDeserialize>(
+    program_id: &Pubkey,
+    return_data: Option<(Pubkey, Vec<u8>)>,
+) -> Option<T> {
+    match return_data {
+        Some((returned_by, data)) if &returned_by == program_id => T::try_from_slice(&data).ok(),
+        _ => None,
+    }
+}
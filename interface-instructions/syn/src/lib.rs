@@ -2,11 +2,30 @@
 //! necessary components for Solana program interface implementations
 
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use spl_interface_instructions_registry::{
-    error::SplInterfaceError, evaluate_interface_instructions, InterfaceInstruction,
+    accounts::{evaluate_interface_account, InterfaceAccount},
+    custom_discriminator,
+    embedded::encode_manifest,
+    error::SplInterfaceError,
+    evaluate_program_interfaces,
+    events::{evaluate_interface_event, InterfaceEvent},
+    interface_errors::find_interface_error,
+    lockfile::{
+        diff_lock_entries, normalize_layout, parse_lockfile, render_lockfile, update_lock_entries,
+        LockEntry, LOCKFILE_NAME, UPDATE_LOCK_ENV,
+    },
+    manifest::InterfaceManifest,
+    qualified_namespace, registered_interface, to_pascal_case, to_snake_case, ImplementedInterface,
+    InterfaceInstruction, RequiredArgType, TrailingArgsPolicy, UnimplementedInstruction,
+};
+use std::{path::Path, sync::Mutex};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Fields, Ident, Item, ItemEnum, ItemFn, ItemMod, ItemStruct, LitInt, LitStr, Token,
+    Type, Variant,
 };
-use syn::{parse::Parse, Attribute, ItemEnum, ItemFn};
 
 /// "Builder" struct for the macro attribute that will run
 /// the necessary checks and then generate the necessary
@@ -19,7 +38,7 @@ pub struct InterfaceInstructionBuilder {
 }
 
 impl TryFrom<ItemEnum> for InterfaceInstructionBuilder {
-    type Error = SplInterfaceError;
+    type Error = syn::Error;
 
     fn try_from(item_enum: ItemEnum) -> Result<Self, Self::Error> {
         let pack_unpack = process_enum(&item_enum)?;
@@ -32,12 +51,7 @@ impl TryFrom<ItemEnum> for InterfaceInstructionBuilder {
 
 impl Parse for InterfaceInstructionBuilder {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        ItemEnum::parse(input)?.try_into().map_err(|e| {
-            syn::Error::new(
-                input.span(),
-                format!("Failed to parse interface instructions: {}", e),
-            )
-        })
+        ItemEnum::parse(input)?.try_into()
     }
 }
 
@@ -57,105 +71,1407 @@ impl From<&InterfaceInstructionBuilder> for TokenStream {
     }
 }
 
+/// "Builder" struct for the derive macro that will check an
+/// account struct against its interface account and implement
+/// the discriminator-checked account (de)serialization
+#[derive(Debug)]
+pub struct InterfaceAccountBuilder {
+    pub item_struct: ItemStruct,
+    pub account_pack: TokenStream,
+}
+
+impl TryFrom<ItemStruct> for InterfaceAccountBuilder {
+    type Error = syn::Error;
+
+    fn try_from(item_struct: ItemStruct) -> Result<Self, Self::Error> {
+        let account_pack = process_account_struct(&item_struct)?;
+        Ok(Self {
+            item_struct,
+            account_pack,
+        })
+    }
+}
+
+impl Parse for InterfaceAccountBuilder {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        ItemStruct::parse(input)?.try_into()
+    }
+}
+
+impl ToTokens for InterfaceAccountBuilder {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.account_pack.clone());
+    }
+}
+
+/// "Builder" struct for the derive macro that will check an
+/// event struct against its interface event and implement
+/// emitting and decoding the event
+#[derive(Debug)]
+pub struct InterfaceEventBuilder {
+    pub item_struct: ItemStruct,
+    pub event_pack: TokenStream,
+}
+
+impl TryFrom<ItemStruct> for InterfaceEventBuilder {
+    type Error = syn::Error;
+
+    fn try_from(item_struct: ItemStruct) -> Result<Self, Self::Error> {
+        let event_pack = process_event_struct(&item_struct)?;
+        Ok(Self {
+            item_struct,
+            event_pack,
+        })
+    }
+}
+
+impl Parse for InterfaceEventBuilder {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        ItemStruct::parse(input)?.try_into()
+    }
+}
+
+impl ToTokens for InterfaceEventBuilder {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.event_pack.clone());
+    }
+}
+
+/// "Builder" struct for the derive macro that will map a program's
+/// error enum onto interface error codes
+#[derive(Debug)]
+pub struct InterfaceErrorBuilder {
+    pub item_enum: ItemEnum,
+    pub error_codes: TokenStream,
+}
+
+impl TryFrom<ItemEnum> for InterfaceErrorBuilder {
+    type Error = syn::Error;
+
+    fn try_from(item_enum: ItemEnum) -> Result<Self, Self::Error> {
+        let error_codes = process_error_enum(&item_enum)?;
+        Ok(Self {
+            item_enum,
+            error_codes,
+        })
+    }
+}
+
+impl Parse for InterfaceErrorBuilder {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        ItemEnum::parse(input)?.try_into()
+    }
+}
+
+impl ToTokens for InterfaceErrorBuilder {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.error_codes.clone());
+    }
+}
+
+/// "Builder" struct for the derive macro that describes a struct
+/// used as an instruction's payload (`Variant(Args)`), so that its
+/// fields can be checked against the interface
+#[derive(Debug)]
+pub struct InterfaceArgsBuilder {
+    pub item_struct: ItemStruct,
+    pub interface_args: TokenStream,
+}
+
+impl TryFrom<ItemStruct> for InterfaceArgsBuilder {
+    type Error = syn::Error;
+
+    fn try_from(item_struct: ItemStruct) -> Result<Self, Self::Error> {
+        let interface_args = generate_interface_args(&item_struct)?;
+        Ok(Self {
+            item_struct,
+            interface_args,
+        })
+    }
+}
+
+impl Parse for InterfaceArgsBuilder {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        ItemStruct::parse(input)?.try_into()
+    }
+}
+
+impl ToTokens for InterfaceArgsBuilder {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.interface_args.clone());
+    }
+}
+
+/// Generate the `InterfaceArgs` implementation for an args struct
+fn generate_interface_args(item_struct: &ItemStruct) -> syn::Result<TokenStream> {
+    let ident = &item_struct.ident;
+    let mut args = vec![];
+    for field in &item_struct.fields {
+        // Unnamed (tuple) fields have an empty name and are matched by
+        // position only
+        let name = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => String::new(),
+        };
+        let name = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("interface_arg"))
+            .map(|attr| attr.parse_args::<Ident>().map(|i| i.to_string()))
+            .transpose()?
+            .unwrap_or(name);
+        let arg_type = RequiredArgType::try_from_type(&field.ty)
+            .ok_or_else(|| {
+                syn::Error::new_spanned(&field.ty, "Unsupported interface argument type")
+            })?
+            .to_string();
+        args.push(quote! { (#name, #arg_type) });
+    }
+    let lock_entry = LockEntry {
+        item: ident.to_string(),
+        variant: None,
+        discriminators: vec![],
+        legacy_tag: None,
+        layout: fields_layout(&item_struct.fields),
+    };
+    let lockfile = check_lockfile(ident, vec![lock_entry])?;
+    Ok(quote! {
+        impl spl_interface_instructions::InterfaceArgs for #ident {
+            const ARGS: &'static [(&'static str, &'static str)] = &[#(#args),*];
+        }
+        #lockfile
+    })
+}
+
 /// Validate the interface instructions from a collection of
 /// defined functions (Anchor)
 pub fn process_functions(functions: Vec<&ItemFn>) -> Result<(), SplInterfaceError> {
+    evaluate_functions(functions, vec![], None).map(|_| ())
+}
+
+/// Validate the interface instructions from a program module,
+/// honoring any `#[interface_unimplemented(..)]` and `#[implements(..)]`
+/// annotations on the module, and return the program's interface
+/// manifest (Anchor)
+///
+/// The manifest lists each unimplemented instruction as
+/// `Unsupported`, so that the dispatcher can route its discriminator
+/// to `InterfaceProgramError::UnsupportedInterfaceInstruction`
+pub fn process_module(item_mod: &ItemMod) -> Result<InterfaceManifest, SplInterfaceError> {
+    let functions = match &item_mod.content {
+        Some((_, items)) => items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(func) => Some(func),
+                _ => None,
+            })
+            .collect(),
+        None => vec![],
+    };
+    let unimplemented_instructions =
+        extract_unimplemented_from_attributes(&item_mod.attrs).map_err(parse_error)?;
+    let implemented_interfaces =
+        extract_implemented_from_attributes(&item_mod.attrs).map_err(parse_error)?;
+    evaluate_functions(
+        functions,
+        unimplemented_instructions,
+        implemented_interfaces,
+    )
+}
+
+/// Validate the interface instructions from a program module and
+/// implement `Implements<I>` on the program's type for every fully
+/// implemented interface (Anchor)
+///
+/// Anchor generates the program's type as `crate::program::<Name>`,
+/// named after the module in `PascalCase`
+pub fn process_module_implements(item_mod: &ItemMod) -> Result<TokenStream, SplInterfaceError> {
+    let manifest = process_module(item_mod)?;
+    let program_type = Ident::new(
+        &to_pascal_case(&item_mod.ident.to_string()),
+        item_mod.ident.span(),
+    );
+    Ok(generate_implements(
+        &quote! { crate::program::#program_type },
+        &manifest,
+    ))
+}
+
+/// Evaluates the interface instructions from a collection of
+/// defined functions (Anchor)
+fn evaluate_functions(
+    functions: Vec<&ItemFn>,
+    unimplemented_instructions: Vec<UnimplementedInstruction>,
+    implemented_interfaces: Option<Vec<ImplementedInterface>>,
+) -> Result<InterfaceManifest, SplInterfaceError> {
     let mut declared_instructions = vec![];
     for func in functions {
-        if let Some(interface_attr) = func
-            .attrs
-            .iter()
-            .find(|attr| attr.path().is_ident("interface"))
-        {
-            if let Ok((interface_namespace, instruction_namespace)) =
-                extract_interface_from_attribute(interface_attr)
-            {
-                declared_instructions.push(InterfaceInstruction::from_item_fn(
-                    &interface_namespace,
-                    &instruction_namespace,
+        let annotations = extract_interfaces_from_attributes(&func.attrs).map_err(parse_error)?;
+        for annotation in annotations {
+            declared_instructions.push(
+                InterfaceInstruction::from_item_fn(
+                    &annotation.interface_namespace,
+                    annotation.interface_version,
+                    &annotation.instruction_namespace,
                     func,
-                ));
-            }
+                )
+                .map_err(parse_error)?,
+            );
         }
     }
-    evaluate_interface_instructions(declared_instructions)
+    evaluate_program_interfaces(
+        declared_instructions,
+        unimplemented_instructions,
+        implemented_interfaces,
+    )
+}
+
+/// Reports a parse error to callers that only take `SplInterfaceError`
+/// (Anchor)
+fn parse_error(e: syn::Error) -> SplInterfaceError {
+    println!("Error parsing interface attribute: {}", e);
+    SplInterfaceError::ParseError
+}
+
+/// Converts a failed interface evaluation into a compile error on
+/// `tokens`, where the details were already printed by the registry
+fn evaluation_error<T: ToTokens>(tokens: T, kind: &str, e: SplInterfaceError) -> syn::Error {
+    syn::Error::new_spanned(tokens, format!("Failed to parse {}: {}", kind, e))
+}
+
+/// Generate an `Implements<I>` impl on a program's type for every
+/// interface in its manifest with no unsupported required instructions
+fn generate_implements(target: &TokenStream, manifest: &InterfaceManifest) -> TokenStream {
+    let impls = manifest
+        .interfaces
+        .iter()
+        .filter(|interface| interface.is_fully_implemented())
+        .filter_map(|interface| registered_interface(&interface.qualified_namespace()))
+        .map(|interface| {
+            let interface_type = Ident::new(interface.type_name, proc_macro2::Span::call_site());
+            quote! {
+                impl spl_interface_instructions::Implements<
+                    spl_interface_instructions::instructions::#interface_type
+                > for #target {}
+            }
+        });
+    quote! { #(#impls)* }
+}
+
+/// Generate a `static` holding the encoded manifest, so that it's
+/// embedded in the program's ELF and readable with
+/// `embedded::read_embedded_manifests` (`embed-manifest` feature)
+///
+/// The symbol is named after the program's instruction enum
+/// (ie. `SPL_INTERFACE_MANIFEST_TOKEN_INSTRUCTION`), and exported so
+/// the linker keeps it. Like the entrypoint, it's left out with the
+/// program's `no-entrypoint` feature, so that a program depending on
+/// another doesn't embed (or clash with) its manifest. The embedding
+/// crate must declare that feature (or its cfg), or `unexpected_cfgs`
+/// fires
+pub fn embed_manifest(program: &Ident, manifest: &InterfaceManifest) -> syn::Result<TokenStream> {
+    let data = encode_manifest(manifest)
+        .map_err(|e| syn::Error::new_spanned(program, format!("Failed to embed manifest: {e}")))?;
+    let len = data.len();
+    let symbol = Ident::new(
+        &format!(
+            "SPL_INTERFACE_MANIFEST_{}",
+            to_snake_case(&program.to_string()).to_uppercase()
+        ),
+        program.span(),
+    );
+    Ok(quote! {
+        #[doc(hidden)]
+        #[cfg(not(feature = "no-entrypoint"))]
+        #[no_mangle]
+        pub static #symbol: [u8; #len] = [#(#data),*];
+    })
 }
 
 /// Validate the interface instructions from a defined
 /// instruction enum and implement the required
 /// traits (Native, Shank)
-fn process_enum(item_enum: &ItemEnum) -> Result<TokenStream, SplInterfaceError> {
+fn process_enum(item_enum: &ItemEnum) -> syn::Result<TokenStream> {
     let mut declared_instructions = vec![];
+    let mut discriminators = vec![];
+    let mut args_checks = vec![];
+    let mut return_data_setters: Vec<(String, TokenStream)> = vec![];
+    for variant in &item_enum.variants {
+        let annotations = extract_interfaces_from_attributes(&variant.attrs)?;
+        for annotation in &annotations {
+            let declared_ix = match args_struct_payload(variant) {
+                Some(args_type) => {
+                    let declared_ix = InterfaceInstruction::from_args_struct(
+                        &annotation.interface_namespace,
+                        annotation.interface_version,
+                        &annotation.instruction_namespace,
+                    );
+                    args_checks.push(generate_args_check(args_type, &declared_ix));
+                    declared_ix
+                }
+                None => InterfaceInstruction::from_variant(
+                    &annotation.interface_namespace,
+                    annotation.interface_version,
+                    &annotation.instruction_namespace,
+                    variant,
+                )?,
+            };
+            if let Some(setter) = generate_return_data_setter(&declared_ix) {
+                if !return_data_setters
+                    .iter()
+                    .any(|(name, _)| name == &setter.0)
+                {
+                    return_data_setters.push(setter);
+                }
+            }
+            declared_instructions.push(declared_ix);
+        }
+        discriminators.push(variant_discriminators(variant, &annotations)?);
+    }
+    let unimplemented_instructions = extract_unimplemented_from_attributes(&item_enum.attrs)?;
+    let implemented_interfaces = extract_implemented_from_attributes(&item_enum.attrs)?;
+    let legacy_discriminators = extract_legacy_discriminators(item_enum, &discriminators)?;
+    let lockfile = check_lockfile(
+        &item_enum.ident,
+        build_lock_entries(item_enum, &discriminators, legacy_discriminators),
+    )?;
+    evaluate_program_interfaces(
+        declared_instructions,
+        unimplemented_instructions.clone(),
+        implemented_interfaces,
+    )
+    .map_err(|e| evaluation_error(&item_enum.ident, "interface instructions", e))
+    .and_then(|manifest| {
+        let implements = generate_implements(&item_enum.ident.to_token_stream(), &manifest);
+        let embedded_manifest = cfg!(feature = "embed-manifest")
+            .then(|| embed_manifest(&item_enum.ident, &manifest))
+            .transpose()?;
+        let pack_unpack = generate_pack_unpack(
+            item_enum,
+            &discriminators,
+            &unimplemented_instructions,
+            legacy_discriminators,
+        );
+        let ident = &item_enum.ident;
+        let setters = return_data_setters.iter().map(|(_, setter)| setter);
+        let return_data = (!return_data_setters.is_empty()).then(|| {
+            quote! {
+                impl #ident {
+                    #(#setters)*
+                }
+            }
+        });
+        Ok(quote! {
+            #pack_unpack
+            #implements
+            #embedded_manifest
+            #(#args_checks)*
+            #return_data
+            #lockfile
+        })
+    })
+}
+
+/// The discriminators routing to an instruction enum variant
+struct VariantDiscriminators {
+    /// Every discriminator `unpack` accepts for the variant
+    unpack: Vec<[u8; 8]>,
+    /// The canonical discriminator `pack` writes for the variant
+    pack: [u8; 8],
+}
+
+/// Returns the discriminators routing to a variant, which are hashed
+/// under the `global` namespace if it has no interface annotations
+fn variant_discriminators(
+    variant: &Variant,
+    annotations: &[InterfaceAnnotation],
+) -> syn::Result<VariantDiscriminators> {
+    if annotations.is_empty() {
+        let discriminator = custom_discriminator(&to_snake_case(&variant.ident.to_string()));
+        return Ok(VariantDiscriminators {
+            unpack: vec![discriminator],
+            pack: discriminator,
+        });
+    }
+    let annotation_discriminators: Vec<[u8; 8]> = annotations
+        .iter()
+        .map(InterfaceAnnotation::discriminator)
+        .collect();
+    // Stable discriminators may coincide across versions
+    let mut unpack: Vec<[u8; 8]> = vec![];
+    for discriminator in &annotation_discriminators {
+        if !unpack.contains(discriminator) {
+            unpack.push(*discriminator);
+        }
+    }
+    let pack = match unpack.len() {
+        1 => unpack[0],
+        _ => annotation_discriminators[canonical_annotation_index(variant, annotations)?],
+    };
+    Ok(VariantDiscriminators { unpack, pack })
+}
+
+/// Returns the index of the annotation named by
+/// `#[interface_canonical(..)]`, whose discriminator is used to pack
+/// a variant with several distinct discriminators
+fn canonical_annotation_index(
+    variant: &Variant,
+    annotations: &[InterfaceAnnotation],
+) -> syn::Result<usize> {
+    match variant
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("interface_canonical"))
+    {
+        Some(attr) => {
+            let canonical = extract_interface_from_attribute(attr)?;
+            annotations
+                .iter()
+                .position(|annotation| annotation == &canonical)
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        attr,
+                        "`interface_canonical` must name one of the variant's interface annotations",
+                    )
+                })
+        }
+        None => Err(syn::Error::new_spanned(
+            &variant.ident,
+            "Variant has several interface annotations. Choose the one used by `pack` with `#[interface_canonical(..)]`",
+        )),
+    }
+}
+
+/// Validate an account struct against the interface account named by
+/// its `#[interface_account(..)]` annotation and implement
+/// `InterfaceAccountPack`
+fn process_account_struct(item_struct: &ItemStruct) -> syn::Result<TokenStream> {
+    let attr = item_struct
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("interface_account"))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &item_struct.ident,
+                "Missing `#[interface_account(..)]` attribute",
+            )
+        })?;
+    let annotation = attr.parse_args_with(InterfaceAnnotation::parse_any)?;
+    let declared_account = InterfaceAccount::from_item_struct(
+        &annotation.interface_namespace,
+        annotation.interface_version,
+        &annotation.instruction_namespace,
+        item_struct,
+    )?;
+    evaluate_interface_account(&declared_account)
+        .map_err(|e| evaluation_error(attr, "interface account", e))?;
+    let ident = &item_struct.ident;
+    let account_pack = generate_discriminated_pack(
+        item_struct,
+        quote! { spl_interface_instructions::InterfaceAccountPack },
+        &declared_account.discriminator(),
+        quote! { solana_program::program_error::ProgramError::InvalidAccountData },
+    );
+    Ok(quote! {
+        #account_pack
+
+        impl #ident {
+            /// Deserializes the account from its data, checking its
+            /// interface discriminator
+            pub fn try_from_slice(data: &[u8]) -> Result<Self, solana_program::program_error::ProgramError> {
+                <Self as spl_interface_instructions::InterfaceAccountPack>::unpack(data)
+            }
+        }
+    })
+}
+
+/// Validate an event struct against the interface event named by its
+/// `#[interface_event(..)]` annotation and implement
+/// `InterfaceEventPack`
+fn process_event_struct(item_struct: &ItemStruct) -> syn::Result<TokenStream> {
+    let attr = item_struct
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("interface_event"))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &item_struct.ident,
+                "Missing `#[interface_event(..)]` attribute",
+            )
+        })?;
+    let annotation = attr.parse_args_with(InterfaceAnnotation::parse_any)?;
+    let declared_event = InterfaceEvent::from_item_struct(
+        &annotation.interface_namespace,
+        annotation.interface_version,
+        &annotation.instruction_namespace,
+        item_struct,
+    )?;
+    evaluate_interface_event(&declared_event)
+        .map_err(|e| evaluation_error(attr, "interface event", e))?;
+    Ok(generate_discriminated_pack(
+        item_struct,
+        quote! { spl_interface_instructions::InterfaceEventPack },
+        &declared_event.discriminator(),
+        quote! { solana_program::program_error::ProgramError::InvalidArgument },
+    ))
+}
+
+/// Generate the implementation of a discriminator-prefixed pack trait
+/// (`InterfaceAccountPack`, `InterfaceEventPack`) for a struct, where
+/// `invalid_data` is returned for data that doesn't match
+///
+/// Any data past the declared fields is ignored, since accounts are
+/// often allocated with room to grow
+fn generate_discriminated_pack(
+    item_struct: &ItemStruct,
+    pack_trait: TokenStream,
+    discriminator: &[u8; 8],
+    invalid_data: TokenStream,
+) -> TokenStream {
+    let ident = &item_struct.ident;
+    let deserialize = quote! {
+        borsh::BorshDeserialize::deserialize(&mut data)
+            .map_err(|_| #invalid_data)?
+    };
+    let (construct, bindings): (TokenStream, Vec<TokenStream>) = match &item_struct.fields {
+        Fields::Named(fields) => {
+            let names: Vec<&Ident> = fields
+                .named
+                .iter()
+                .filter_map(|f| f.ident.as_ref())
+                .collect();
+            (
+                quote! { Self { #(#names: #deserialize),* } },
+                names.iter().map(|n| quote! { &self.#n }).collect(),
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let deserializers = fields.unnamed.iter().map(|_| &deserialize);
+            (
+                quote! { Self ( #(#deserializers),* ) },
+                (0..fields.unnamed.len())
+                    .map(|i| {
+                        let index = syn::Index::from(i);
+                        quote! { &self.#index }
+                    })
+                    .collect(),
+            )
+        }
+        Fields::Unit => (quote! { Self }, vec![]),
+    };
+    quote! {
+        impl #pack_trait for #ident {
+            const DISCRIMINATOR: [u8; 8] = [#(#discriminator),*];
+            fn unpack(data: &[u8]) -> Result<Self, solana_program::program_error::ProgramError> {
+                if data.len() < 8 || data[..8] != Self::DISCRIMINATOR {
+                    return Err(#invalid_data);
+                }
+                let mut data = &data[8..];
+                Ok(#construct)
+            }
+            fn pack<W: std::io::Write>(&self, writer: &mut W) -> Result<(), solana_program::program_error::ProgramError> {
+                writer
+                    .write_all(&Self::DISCRIMINATOR)
+                    .map_err(|e| solana_program::program_error::ProgramError::BorshIoError(e.to_string()))?;
+                #(
+                    borsh::BorshSerialize::serialize(#bindings, writer)
+                        .map_err(|e| solana_program::program_error::ProgramError::BorshIoError(e.to_string()))?;
+                )*
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Map a program's error enum onto interface error codes, where each
+/// variant annotated with `#[interface_error(..)]` returns the
+/// interface error's code and any other variant keeps its own
+/// discriminant
+fn process_error_enum(item_enum: &ItemEnum) -> syn::Result<TokenStream> {
+    let ident = &item_enum.ident;
+    let mut arms = vec![];
+    let mut discriminant = quote! { 0u32 };
     for variant in &item_enum.variants {
-        if let Some(interface_attr) = variant
+        let variant_ident = &variant.ident;
+        let pattern = match &variant.fields {
+            Fields::Named(_) => quote! { Self::#variant_ident { .. } },
+            Fields::Unnamed(_) => quote! { Self::#variant_ident(..) },
+            Fields::Unit => quote! { Self::#variant_ident },
+        };
+        if let Some((_, expr)) = &variant.discriminant {
+            discriminant = quote! { (#expr) as u32 };
+        }
+        let code = match variant
             .attrs
             .iter()
-            .find(|attr| attr.path().is_ident("interface"))
+            .find(|attr| attr.path().is_ident("interface_error"))
         {
-            if let Ok((interface_namespace, instruction_namespace)) =
-                extract_interface_from_attribute(interface_attr)
-            {
-                declared_instructions.push(InterfaceInstruction::from_variant(
-                    &interface_namespace,
-                    &instruction_namespace,
-                    variant,
-                ));
+            Some(attr) => {
+                let annotation = attr.parse_args_with(InterfaceAnnotation::parse_any)?;
+                let code = find_interface_error(
+                    &qualified_namespace(
+                        &annotation.interface_namespace,
+                        annotation.interface_version,
+                    ),
+                    &annotation.interface_namespace,
+                    &annotation.instruction_namespace,
+                )
+                .map_err(|e| evaluation_error(attr, "interface errors", e))?
+                .code();
+                quote! { #code }
+            }
+            None => discriminant.clone(),
+        };
+        arms.push(quote! { #pattern => #code, });
+        discriminant = quote! { #discriminant + 1 };
+    }
+    Ok(quote! {
+        impl #ident {
+            /// Returns the error's `ProgramError::Custom` code
+            pub fn error_code(&self) -> u32 {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+
+        impl From<#ident> for solana_program::program_error::ProgramError {
+            fn from(e: #ident) -> Self {
+                solana_program::program_error::ProgramError::Custom(e.error_code())
+            }
+        }
+    })
+}
+
+/// Returns the payload type of a variant wrapping a single struct
+/// (`Variant(Args)`), if it is one
+fn args_struct_payload(variant: &Variant) -> Option<&Type> {
+    match &variant.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let ty = &fields.unnamed[0].ty;
+            RequiredArgType::try_from_type(ty).is_none().then_some(ty)
+        }
+        _ => None,
+    }
+}
+
+/// Generate a compile-time check that an args struct's fields match
+/// the interface instruction's required arguments
+fn generate_args_check(args_type: &Type, interface_ix: &InterfaceInstruction) -> TokenStream {
+    let required = interface_ix.required_args.iter().map(|(name, arg_type)| {
+        let arg_type = arg_type.to_string();
+        quote! { (#name, #arg_type) }
+    });
+    let allow_trailing = registered_interface(&interface_ix.qualified_interface_namespace())
+        .map(|interface| interface.trailing_args == TrailingArgsPolicy::Allow)
+        .unwrap_or(false);
+    let message = format!(
+        "The fields of `{}` do not match the arguments of interface instruction `{}::{}`",
+        args_type.to_token_stream(),
+        interface_ix.qualified_interface_namespace(),
+        interface_ix.instruction_namespace
+    );
+    quote! {
+        const _: () = assert!(
+            spl_interface_instructions::args_match(
+                <#args_type as spl_interface_instructions::InterfaceArgs>::ARGS,
+                &[#(#required),*],
+                #allow_trailing,
+            ),
+            #message
+        );
+    }
+}
+
+/// Generate a `set_<instruction>_return_data` function for a view
+/// instruction, which borsh-encodes its output and hands it to
+/// `set_return_data`, keyed by the function's name
+fn generate_return_data_setter(
+    declared_ix: &InterfaceInstruction,
+) -> Option<(String, TokenStream)> {
+    let interface = registered_interface(&declared_ix.qualified_interface_namespace())?;
+    let returns = (interface.instructions)()
+        .into_iter()
+        .find(|ix| ix.instruction_namespace == declared_ix.instruction_namespace)?
+        .returns;
+    let return_type = match returns.as_slice() {
+        [] => return None,
+        [single] => Type::from(single).to_token_stream(),
+        many => {
+            let types = many.iter().map(Type::from);
+            quote! { (#(#types),*) }
+        }
+    };
+    let name = format!("set_{}_return_data", declared_ix.instruction_namespace);
+    let fn_ident = Ident::new(&name, proc_macro2::Span::call_site());
+    let doc = format!(
+        "Sets the return data of interface view `{}::{}`",
+        declared_ix.qualified_interface_namespace(),
+        declared_ix.instruction_namespace
+    );
+    let setter = quote! {
+        #[doc = #doc]
+        pub fn #fn_ident(value: &#return_type) -> Result<(), solana_program::program_error::ProgramError> {
+            let data = borsh::BorshSerialize::try_to_vec(value)
+                .map_err(|e| solana_program::program_error::ProgramError::BorshIoError(e.to_string()))?;
+            solana_program::program::set_return_data(&data);
+            Ok(())
+        }
+    };
+    Some((name, setter))
+}
+
+/// A parsed interface namespace with an optional version
+struct InterfaceNamespace {
+    /// The interface's namespace
+    namespace: String,
+    /// The interface's version (`1` unless specified)
+    version: u8,
+}
+
+impl Parse for InterfaceNamespace {
+    /// Parses `interface_namespace[@version]`, where the namespace
+    /// may span several `::` segments
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let path = InterfacePath::parse(input)?;
+        match path.version {
+            Some((_, index)) if index + 1 != path.segments.len() => Err(syn::Error::new(
+                span,
+                "The version must follow the interface namespace",
+            )),
+            _ => Ok(Self {
+                namespace: path.segments.join("::"),
+                version: path.version.map_or(1, |(version, _)| version),
+            }),
+        }
+    }
+}
+
+/// A `::`-separated path, where one segment may carry an
+/// `@version` suffix
+struct InterfacePath {
+    /// The path's segments
+    segments: Vec<String>,
+    /// The version and the index of the segment it follows
+    version: Option<(u8, usize)>,
+}
+
+impl Parse for InterfacePath {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut segments = vec![];
+        let mut version = None;
+        loop {
+            let segment: Ident = input.parse()?;
+            segments.push(segment.to_string());
+            if input.peek(Token![@]) {
+                input.parse::<Token![@]>()?;
+                let lit: LitInt = input.parse()?;
+                if version.is_some() {
+                    return Err(syn::Error::new(lit.span(), "Version specified twice"));
+                }
+                version = Some((parse_version(&lit)?, segments.len() - 1));
+            }
+            if !input.peek(Token![::]) {
+                break;
+            }
+            input.parse::<Token![::]>()?;
+        }
+        Ok(Self { segments, version })
+    }
+}
+
+/// Parses an interface version, which starts at 1
+fn parse_version(lit: &LitInt) -> syn::Result<u8> {
+    match lit.base10_parse::<u8>()? {
+        0 => Err(syn::Error::new(lit.span(), "Versions start at 1")),
+        v => Ok(v),
+    }
+}
+
+/// A parsed `#[interface(..)]` annotation
+#[derive(PartialEq)]
+struct InterfaceAnnotation {
+    /// The interface's namespace
+    interface_namespace: String,
+    /// The interface's version (`1` unless specified)
+    interface_version: u8,
+    /// The instruction's namespace
+    instruction_namespace: String,
+}
+
+impl Parse for InterfaceAnnotation {
+    /// Parses `interface_namespace[@version]::instruction_namespace`,
+    /// where the interface namespace may span several `::` segments
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let mut path = InterfacePath::parse(input)?;
+        if path.segments.len() < 2 {
+            return Err(syn::Error::new(
+                span,
+                "Expected `interface_namespace::instruction_namespace`",
+            ));
+        }
+        let instruction_namespace = path.segments.pop().unwrap();
+        let interface_version = match path.version {
+            Some((version, index)) if index == path.segments.len() - 1 => version,
+            Some(_) => {
+                return Err(syn::Error::new(
+                    span,
+                    "The version must follow the interface namespace",
+                ))
+            }
+            None => 1,
+        };
+        Ok(Self {
+            interface_namespace: path.segments.join("::"),
+            interface_version,
+            instruction_namespace,
+        })
+    }
+}
+
+impl InterfaceAnnotation {
+    /// Parses the key-value form of an annotation:
+    /// `namespace = "..", instruction = ".."[, version = N]`
+    fn parse_key_value(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let mut namespace: Option<InterfaceNamespace> = None;
+        let mut instruction: Option<Ident> = None;
+        let mut version: Option<u8> = None;
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let duplicate = || syn::Error::new(key.span(), format!("Duplicate key `{}`", key));
+            match key.to_string().as_str() {
+                "namespace" => {
+                    let lit: LitStr = input.parse()?;
+                    if namespace.replace(lit.parse()?).is_some() {
+                        return Err(duplicate());
+                    }
+                }
+                "instruction" => {
+                    let lit: LitStr = input.parse()?;
+                    if instruction.replace(lit.parse()?).is_some() {
+                        return Err(duplicate());
+                    }
+                }
+                "version" => {
+                    let lit: LitInt = input.parse()?;
+                    if version.replace(parse_version(&lit)?).is_some() {
+                        return Err(duplicate());
+                    }
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!(
+                            "Unknown key `{}`, expected `namespace`, `instruction` or `version`",
+                            key
+                        ),
+                    ))
+                }
             }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        let namespace = namespace.ok_or_else(|| syn::Error::new(span, "Missing `namespace`"))?;
+        let instruction =
+            instruction.ok_or_else(|| syn::Error::new(span, "Missing `instruction`"))?;
+        let interface_version = match version {
+            Some(_) if namespace.version != 1 => {
+                return Err(syn::Error::new(span, "Version specified twice"))
+            }
+            Some(version) => version,
+            None => namespace.version,
+        };
+        Ok(Self {
+            interface_namespace: namespace.namespace,
+            interface_version,
+            instruction_namespace: instruction.to_string(),
+        })
+    }
+
+    /// Returns the discriminator of the annotated interface instruction
+    fn discriminator(&self) -> [u8; 8] {
+        InterfaceInstruction::from_args_struct(
+            &self.interface_namespace,
+            self.interface_version,
+            &self.instruction_namespace,
+        )
+        .discriminator()
+    }
+
+    /// Parses either the path or the key-value form of an annotation
+    fn parse_any(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            Self::parse_key_value(input)
+        } else {
+            Self::parse(input)
+        }
+    }
+}
+
+/// Extracts the instructions listed by any
+/// `#[interface_unimplemented(..)]` annotations, which accept a
+/// comma-separated list of interface instructions
+fn extract_unimplemented_from_attributes(
+    attrs: &[Attribute],
+) -> syn::Result<Vec<UnimplementedInstruction>> {
+    let mut unimplemented_instructions = vec![];
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("interface_unimplemented"))
+    {
+        let annotations =
+            attr.parse_args_with(Punctuated::<InterfaceAnnotation, Token![,]>::parse_terminated)?;
+        for annotation in annotations {
+            unimplemented_instructions.push(UnimplementedInstruction {
+                interface_namespace: annotation.interface_namespace,
+                interface_version: annotation.interface_version,
+                instruction_namespace: annotation.instruction_namespace,
+            });
         }
     }
-    evaluate_interface_instructions(declared_instructions).map(|_| generate_pack_unpack(item_enum))
+    Ok(unimplemented_instructions)
+}
+
+/// Extracts the interfaces listed by any `#[implements(..)]`
+/// annotations, which accept a comma-separated list of interface
+/// namespaces, or `None` if the program doesn't declare them
+fn extract_implemented_from_attributes(
+    attrs: &[Attribute],
+) -> syn::Result<Option<Vec<ImplementedInterface>>> {
+    let mut implemented_interfaces: Option<Vec<ImplementedInterface>> = None;
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("implements"))
+    {
+        let namespaces =
+            attr.parse_args_with(Punctuated::<InterfaceNamespace, Token![,]>::parse_terminated)?;
+        implemented_interfaces
+            .get_or_insert_with(Vec::new)
+            .extend(namespaces.into_iter().map(|n| ImplementedInterface {
+                namespace: n.namespace,
+                version: n.version,
+            }));
+    }
+    Ok(implemented_interfaces)
 }
 
-/// Extracts the interface namespace and instruction namespace
-/// from an attribute annotation
+/// Extracts every `#[interface(..)]` annotation from a set of
+/// attributes, since an instruction may satisfy several interfaces
+fn extract_interfaces_from_attributes(
+    attrs: &[Attribute],
+) -> syn::Result<Vec<InterfaceAnnotation>> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("interface"))
+        .map(extract_interface_from_attribute)
+        .collect()
+}
+
+/// Extracts the interface namespace, version and instruction
+/// namespace from an attribute annotation
 fn extract_interface_from_attribute(
     interface_attr: &Attribute,
-) -> Result<(String, String), SplInterfaceError> {
-    let mut res: (String, String) = (String::default(), String::default());
-    match interface_attr.parse_nested_meta(|meta| {
-        res = (
-            meta.path.segments[0].ident.to_string(),
-            meta.path.segments[1].ident.to_string(),
-        );
-        Ok(())
-    }) {
-        Ok(_) => Ok(res),
-        Err(e) => {
-            println!("Error parsing interface attribute: {}", e);
-            Err(SplInterfaceError::ParseError)
+) -> syn::Result<InterfaceAnnotation> {
+    interface_attr.parse_args_with(InterfaceAnnotation::parse_any)
+}
+
+/// Checks whether an instruction enum opts into accepting legacy
+/// borsh variant tags with `#[interface_legacy_discriminators]`
+///
+/// Since legacy instruction data starts with a one-byte variant index,
+/// an interface discriminator whose first byte is the index of a
+/// variant carrying arguments could be read either way, so such enums
+/// are rejected
+fn extract_legacy_discriminators(
+    item_enum: &ItemEnum,
+    discriminators: &[VariantDiscriminators],
+) -> syn::Result<bool> {
+    let attr = match item_enum
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("interface_legacy_discriminators"))
+    {
+        Some(attr) => attr,
+        None => return Ok(false),
+    };
+    if !cfg!(feature = "legacy-discriminators") {
+        return Err(syn::Error::new_spanned(
+            attr,
+            SplInterfaceError::LegacyDiscriminatorsDisabled,
+        ));
+    }
+    let mut ambiguous: Option<syn::Error> = None;
+    for (variant, variant_discriminators) in item_enum.variants.iter().zip(discriminators) {
+        for discriminator in &variant_discriminators.unpack {
+            if let Some(tagged) = item_enum.variants.iter().nth(discriminator[0] as usize) {
+                if !tagged.fields.is_empty() {
+                    let error = syn::Error::new_spanned(
+                        &variant.ident,
+                        format!(
+                            "{}: the discriminator of `{}` starts with the legacy tag of `{}`: {:?}",
+                            SplInterfaceError::AmbiguousLegacyDiscriminator,
+                            variant.ident,
+                            tagged.ident,
+                            discriminator
+                        ),
+                    );
+                    match &mut ambiguous {
+                        Some(ambiguous) => ambiguous.combine(error),
+                        None => ambiguous = Some(error),
+                    }
+                }
+            }
+        }
+    }
+    match ambiguous {
+        Some(ambiguous) => Err(ambiguous),
+        None => Ok(true),
+    }
+}
+
+/// Returns the lock entries of an instruction enum: each variant's
+/// discriminators and field layout, as checked against
+/// `interfaces.lock`
+pub fn instruction_lock_entries(item_enum: &ItemEnum) -> syn::Result<Vec<LockEntry>> {
+    let mut discriminators = vec![];
+    for variant in &item_enum.variants {
+        let annotations = extract_interfaces_from_attributes(&variant.attrs)?;
+        discriminators.push(variant_discriminators(variant, &annotations)?);
+    }
+    let legacy_discriminators = extract_legacy_discriminators(item_enum, &discriminators)?;
+    Ok(build_lock_entries(
+        item_enum,
+        &discriminators,
+        legacy_discriminators,
+    ))
+}
+
+/// Build the lock entries of an instruction enum from its
+/// discriminators
+fn build_lock_entries(
+    item_enum: &ItemEnum,
+    discriminators: &[VariantDiscriminators],
+    legacy_discriminators: bool,
+) -> Vec<LockEntry> {
+    item_enum
+        .variants
+        .iter()
+        .zip(discriminators)
+        .enumerate()
+        .map(|(index, (variant, discriminators))| {
+            let mut locked = vec![discriminators.pack];
+            locked.extend(
+                discriminators
+                    .unpack
+                    .iter()
+                    .filter(|discriminator| **discriminator != discriminators.pack),
+            );
+            LockEntry {
+                item: item_enum.ident.to_string(),
+                variant: Some(variant.ident.to_string()),
+                discriminators: locked,
+                legacy_tag: legacy_discriminators.then_some(index as u8),
+                layout: fields_layout(&variant.fields),
+            }
+        })
+        .collect()
+}
+
+/// Renders fields as they're declared, ie. `{ amount: u64 }` or
+/// `(u64, Pubkey)`, which is their borsh layout
+fn fields_layout(fields: &Fields) -> String {
+    let ty = |field: &syn::Field| -> String {
+        normalize_layout(
+            &field
+                .ty
+                .to_token_stream()
+                .to_string()
+                .split_whitespace()
+                .collect::<String>(),
+        )
+    };
+    match fields {
+        Fields::Named(fields) => {
+            let fields: Vec<String> = fields
+                .named
+                .iter()
+                .map(|field| format!("{}: {}", field.ident.as_ref().unwrap(), ty(field)))
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+        Fields::Unnamed(fields) => {
+            let fields: Vec<String> = fields.unnamed.iter().map(ty).collect();
+            format!("({})", fields.join(", "))
+        }
+        Fields::Unit => String::new(),
+    }
+}
+
+/// The lock entries rewritten while expanding the crate, by lockfile
+/// and item, since items from different modules may share a name
+static UPDATED_LOCK_ENTRIES: Mutex<Vec<(String, String, Vec<LockEntry>)>> = Mutex::new(vec![]);
+
+/// Check an item's lock entries against the crate's `interfaces.lock`,
+/// if it has one, or rewrite them when `SPL_INTERFACE_UPDATE_LOCK=1`
+///
+/// Only breaking changes are errors: added entries are reported with a
+/// `deprecated` warning on the item until the lock is updated, since
+/// proc macros can't emit warnings of their own
+///
+/// Returns tokens making the crate depend on the lock and the
+/// variable, so that changing either expands the derive again
+fn check_lockfile(item: &Ident, current: Vec<LockEntry>) -> syn::Result<TokenStream> {
+    let track_update = quote! {
+        const _: Option<&str> = option_env!(#UPDATE_LOCK_ENV);
+    };
+    let manifest_dir = match std::env::var("CARGO_MANIFEST_DIR") {
+        Ok(manifest_dir) => manifest_dir,
+        Err(_) => return Ok(track_update),
+    };
+    let path = Path::new(&manifest_dir).join(LOCKFILE_NAME);
+    let locked =
+        match std::fs::read_to_string(&path) {
+            Ok(data) => Some(parse_lockfile(&data).map_err(|e| {
+                syn::Error::new_spanned(item, format!("{}: `{}`", e, path.display()))
+            })?),
+            Err(_) => None,
+        };
+    let item_name = item.to_string();
+    let mut outdated = None;
+    if std::env::var(UPDATE_LOCK_ENV).as_deref() == Ok("1") {
+        reject_duplicate_item(item, &path.display().to_string(), &current)?;
+        let entries = update_lock_entries(locked.unwrap_or_default(), &item_name, current);
+        std::fs::write(&path, render_lockfile(&entries)).map_err(|e| {
+            syn::Error::new_spanned(
+                item,
+                format!(
+                    "{}: `{}`: {}",
+                    SplInterfaceError::LockfileUnwritable,
+                    path.display(),
+                    e
+                ),
+            )
+        })?;
+    } else if let Some(locked) = locked {
+        let (breaking, compatible): (Vec<_>, Vec<_>) =
+            diff_lock_entries(&locked, &item_name, &current)
+                .into_iter()
+                .partition(|change| change.is_breaking());
+        if !compatible.is_empty() {
+            let changes: Vec<String> = compatible
+                .iter()
+                .map(|change| format!("  - {}", change))
+                .collect();
+            let note = format!(
+                "The wire format of `{}` has compatible changes not in `{}`:\n{}\nUpdate the lock by building with `{}=1`",
+                item,
+                path.display(),
+                changes.join("\n"),
+                UPDATE_LOCK_ENV
+            );
+            outdated = Some(quote_spanned! {item.span()=>
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    struct InterfacesLockOutdated;
+                    let _ = InterfacesLockOutdated;
+                };
+            });
+        }
+        if !breaking.is_empty() {
+            let changes: Vec<String> = breaking
+                .iter()
+                .map(|change| format!("  - {}", change))
+                .collect();
+            return Err(syn::Error::new_spanned(
+                item,
+                format!(
+                    "The wire format of `{}` does not match `{}`:\n{}\nIf the change is intended, update the lock by building with `{}=1`",
+                    item,
+                    path.display(),
+                    changes.join("\n"),
+                    UPDATE_LOCK_ENV
+                ),
+            ));
+        }
+    } else {
+        return Ok(track_update);
+    }
+    let path = path.display().to_string();
+    Ok(quote! {
+        #track_update
+        const _: &[u8] = include_bytes!(#path);
+        #outdated
+    })
+}
+
+/// Check that no other item of the crate with the same name rewrote
+/// its lock entries, since entries are keyed by name
+///
+/// Expanding the same item again with the same entries is allowed
+fn reject_duplicate_item(item: &Ident, lockfile: &str, current: &[LockEntry]) -> syn::Result<()> {
+    let mut updated = UPDATED_LOCK_ENTRIES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let item_name = item.to_string();
+    match updated
+        .iter()
+        .find(|(path, name, _)| path == lockfile && name == &item_name)
+    {
+        Some((_, _, entries)) if entries.as_slice() != current => Err(syn::Error::new_spanned(
+            item,
+            format!(
+                "Another `{}` already has entries in `{}`, which are keyed by name: rename one of them",
+                item, lockfile
+            ),
+        )),
+        Some(_) => Ok(()),
+        None => {
+            updated.push((lockfile.to_string(), item_name, current.to_vec()));
+            Ok(())
         }
     }
 }
 
 /// Generate the pack and unpack implementations for the
 /// instruction enum declared by the program
-fn generate_pack_unpack(item_enum: &ItemEnum) -> TokenStream {
-    // let ident = &item_enum.ident;
-    // let (unpack_arms, pack_arms) = build_pack_unpack_arms(item_enum);
-    // quote! {
-    //     impl InterfaceInstructionPack for #ident {
-    //         fn unpack(buf: &[u8]) -> Result<Self, solana_program::program_error::ProgramError> {
-    //             let (discrim, rest) = buf.split_at(8);
-    //             if discrim.len() < 8 {
-    //                 return Err(solana_program::program_error::ProgramError::InvalidInstructionData);
-    //             }
-    //             match discrim {
-    //                 #(#unpack_arms)*
-    //                 _ => Err(solana_program::program_error::ProgramError::InvalidInstructionData)
-    //             }
-    //         }
-    //         fn pack<W: std::io::Write>(&self, writer: &mut W) -> Result<(), solana_program::program_error::ProgramError> {
-    //             match self {
-    //                 #(#pack_arms)*
-    //             }
-    //         }
-    //     }
-    // }
-    quote! {}
-}
-
-/// Build the pack and unpack arms for the generated tokens
-fn build_pack_unpack_arms(item_enum: &ItemEnum) -> (Vec<TokenStream>, Vec<TokenStream>) {
-    // TODO
-    (vec![quote! {}], vec![quote! {}])
+///
+/// With legacy discriminators, `unpack` falls back to the borsh
+/// variant tag when no interface discriminator matches, while `pack`
+/// always writes the interface discriminator
+fn generate_pack_unpack(
+    item_enum: &ItemEnum,
+    discriminators: &[VariantDiscriminators],
+    unimplemented_instructions: &[UnimplementedInstruction],
+    legacy_discriminators: bool,
+) -> TokenStream {
+    let ident = &item_enum.ident;
+    let (unpack_arms, legacy_arms, pack_arms) = build_pack_unpack_arms(item_enum, discriminators);
+    let unsupported_arms = unimplemented_instructions.iter().map(|ix| {
+        let discriminator = ix.discriminator();
+        quote! {
+            [#(#discriminator),*] => Err(
+                spl_interface_instructions::error::InterfaceProgramError::UnsupportedInterfaceInstruction.into()
+            ),
+        }
+    });
+    let fallback = if legacy_discriminators {
+        quote! {{
+            let (tag, rest) = buf
+                .split_first()
+                .ok_or(solana_program::program_error::ProgramError::InvalidInstructionData)?;
+            match tag {
+                #(#legacy_arms)*
+                _ => Err(solana_program::program_error::ProgramError::InvalidInstructionData),
+            }
+        }}
+    } else {
+        quote! { Err(solana_program::program_error::ProgramError::InvalidInstructionData) }
+    };
+    let unpack = quote! {
+        if buf.len() < 8 {
+            return #fallback;
+        }
+        let (discrim, rest) = buf.split_at(8);
+        match discrim {
+            #(#unpack_arms)*
+            #(#unsupported_arms)*
+            _ => #fallback,
+        }
+    };
+    quote! {
+        impl spl_interface_instructions::InterfaceInstructionPack for #ident {
+            fn unpack(buf: &[u8]) -> Result<Self, solana_program::program_error::ProgramError> {
+                #unpack
+            }
+            fn pack<W: std::io::Write>(&self, writer: &mut W) -> Result<(), solana_program::program_error::ProgramError> {
+                match self {
+                    #(#pack_arms)*
+                }
+            }
+        }
+    }
+}
+
+/// Build the unpack, legacy unpack and pack arms for the generated
+/// tokens
+fn build_pack_unpack_arms(
+    item_enum: &ItemEnum,
+    discriminators: &[VariantDiscriminators],
+) -> (Vec<TokenStream>, Vec<TokenStream>, Vec<TokenStream>) {
+    let mut unpack_arms = vec![];
+    let mut legacy_arms = vec![];
+    let mut pack_arms = vec![];
+    for (index, (variant, discriminators)) in
+        item_enum.variants.iter().zip(discriminators).enumerate()
+    {
+        let variant_ident = &variant.ident;
+        let deserialize = quote! {
+            borsh::BorshDeserialize::deserialize(&mut data)
+                .map_err(|_| solana_program::program_error::ProgramError::InvalidInstructionData)?
+        };
+        let (unpack_body, pattern, bindings) = match &variant.fields {
+            Fields::Named(fields) => {
+                let names: Vec<&Ident> = fields
+                    .named
+                    .iter()
+                    .filter_map(|f| f.ident.as_ref())
+                    .collect();
+                (
+                    quote! {{
+                        let mut data = rest;
+                        Ok(Self::#variant_ident { #(#names: #deserialize),* })
+                    }},
+                    quote! { Self::#variant_ident { #(#names),* } },
+                    names.iter().map(|n| quote! { #n }).collect::<Vec<_>>(),
+                )
+            }
+            Fields::Unnamed(fields) => {
+                let names: Vec<Ident> = (0..fields.unnamed.len())
+                    .map(|i| Ident::new(&format!("arg_{}", i), variant_ident.span()))
+                    .collect();
+                let deserializers = names.iter().map(|_| &deserialize);
+                (
+                    quote! {{
+                        let mut data = rest;
+                        Ok(Self::#variant_ident ( #(#deserializers),* ))
+                    }},
+                    quote! { Self::#variant_ident ( #(#names),* ) },
+                    names.iter().map(|n| quote! { #n }).collect::<Vec<_>>(),
+                )
+            }
+            Fields::Unit => (
+                quote! { Ok(Self::#variant_ident) },
+                quote! { Self::#variant_ident },
+                vec![],
+            ),
+        };
+        for discriminator in &discriminators.unpack {
+            unpack_arms.push(quote! {
+                [#(#discriminator),*] => #unpack_body,
+            });
+        }
+        let tag = index as u8;
+        legacy_arms.push(quote! {
+            #tag => #unpack_body,
+        });
+        let discriminator = &discriminators.pack;
+        pack_arms.push(quote! {
+            #pattern => {
+                writer
+                    .write_all(&[#(#discriminator),*])
+                    .map_err(|e| solana_program::program_error::ProgramError::BorshIoError(e.to_string()))?;
+                #(
+                    borsh::BorshSerialize::serialize(#bindings, writer)
+                        .map_err(|e| solana_program::program_error::ProgramError::BorshIoError(e.to_string()))?;
+                )*
+                Ok(())
+            }
+        });
+    }
+    (unpack_arms, legacy_arms, pack_arms)
 }
@@ -1,4 +1,7 @@
-use solana_program::pubkey::Pubkey;
+use base64::Engine;
+use borsh::{BorshDeserialize, BorshSerialize};
+use quote::ToTokens;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 use spl_interface_instructions::*;
 
 #[derive(SplInterfaceInstruction)]
@@ -11,6 +14,10 @@ pub enum SampleTokenA {
 
 #[derive(SplInterfaceInstruction)]
 pub enum SampleTokenB {
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u64 },
+    #[interface(srfc20_token::transfer)]
+    Transfer { amount: u64 },
     #[interface(srfc22_associated_token::freeze)]
     Freeze,
     #[interface(srfc22_associated_token::thaw)]
@@ -19,6 +26,10 @@ pub enum SampleTokenB {
 
 #[derive(SplInterfaceInstruction)]
 pub enum SampleTokenC {
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u64 },
+    #[interface(srfc20_token::transfer)]
+    Transfer { amount: u64 },
     #[interface(srfc23_token_metadata::create_metadata)]
     CreateMetadata {
         name: String,
@@ -81,5 +92,1103 @@ pub enum SampleTokenE {
     },
 }
 
+#[derive(SplInterfaceInstruction)]
+pub enum SampleTokenF {
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u64 },
+    #[interface(srfc20_token::transfer)]
+    Transfer { amount: u64 },
+    #[interface(srfc23_token_metadata::create_metadata)]
+    CreateMetadata {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+}
+
+#[derive(SplInterfaceInstruction)]
+pub enum SampleTokenG {
+    #[interface(srfc20_token@2::mint_to)]
+    MintTo { amount: u64 },
+    #[interface(srfc20_token@2::transfer)]
+    Transfer { amount: u64 },
+    #[interface(srfc20_token@2::approve)]
+    Approve { amount: u64 },
+}
+
+#[derive(Debug, PartialEq, SplInterfaceInstruction)]
+#[interface_unimplemented(srfc20_token::transfer)]
+pub enum SampleSoulboundToken {
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u64 },
+}
+
+#[derive(SplInterfaceInstruction)]
+#[implements(srfc20_token, srfc21_token)]
+pub enum SampleTokenI {
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u64 },
+    #[interface(srfc20_token::transfer)]
+    Transfer { amount: u64 },
+    #[interface(srfc21_token::burn)]
+    Burn { amount: u64 },
+}
+
+#[derive(SplInterfaceInstruction)]
+pub enum SampleTokenJ {
+    #[interface(srfc20_token::mint_to)]
+    MintTo {
+        #[interface_arg(amount)]
+        qty: u64,
+    },
+    #[interface(srfc20_token::transfer)]
+    Transfer { amount: u64, memo: String },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, SplInterfaceArgs)]
+pub struct MintToArgs {
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, SplInterfaceArgs)]
+pub struct BurnArgs(pub u64);
+
+#[derive(Debug, PartialEq, SplInterfaceInstruction)]
+pub enum SampleTokenK {
+    #[interface(srfc20_token::mint_to)]
+    MintTo(MintToArgs),
+    #[interface(srfc20_token::transfer)]
+    Transfer(u64),
+    #[interface(srfc21_token::burn)]
+    Burn(BurnArgs),
+}
+
+#[derive(SplInterfaceInstruction)]
+pub enum SampleTokenL {
+    #[interface(namespace = "srfc20_token@2", instruction = "mint_to")]
+    MintTo { amount: u64 },
+    #[interface(namespace = "srfc20_token", instruction = "transfer", version = 2)]
+    Transfer { amount: u64 },
+    #[interface(namespace = "srfc20_token@2", instruction = "approve")]
+    Approve { amount: u64 },
+}
+
+#[derive(Debug, PartialEq, SplInterfaceInstruction)]
+pub enum SampleTokenM {
+    #[interface(srfc20_token::mint_to)]
+    #[interface(srfc20_token@2::mint_to)]
+    MintTo { amount: u64 },
+    #[interface(srfc20_token::transfer)]
+    #[interface(srfc20_token@2::transfer)]
+    Transfer { amount: u64 },
+    #[interface(srfc21_token::burn)]
+    #[interface(srfc20_token@2::approve)]
+    #[interface_canonical(srfc21_token::burn)]
+    Burn { amount: u64 },
+}
+
+#[derive(BorshSerialize, Debug, PartialEq, SplInterfaceInstruction)]
+#[interface_legacy_discriminators]
+pub enum SampleLegacyToken {
+    #[interface(srfc20_token::mint_to)]
+    MintTo {
+        amount: u64,
+    },
+    #[interface(srfc20_token::transfer)]
+    Transfer {
+        amount: u64,
+    },
+    Pause,
+}
+
+#[derive(Debug, PartialEq, SplInterfaceAccount)]
+#[interface_account(srfc20_token::token_account)]
+pub struct SampleTokenAccount {
+    pub mint: Pubkey,
+    #[interface_arg(owner)]
+    pub authority: Pubkey,
+    pub amount: u64,
+    // Fields after the interface's prefix may have any type
+    pub is_frozen: bool,
+    pub delegate: Option<Pubkey>,
+}
+
+#[derive(Clone, Debug, PartialEq, SplInterfaceEvent)]
+#[interface_event(srfc20_token::transfer)]
+pub struct SampleTransferEvent {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub memo: Vec<u8>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, SplInterfaceError)]
+#[repr(u32)]
+pub enum SampleTokenError {
+    #[interface_error(example_token_extensions::insufficient_funds)]
+    InsufficientFunds,
+    #[interface_error(example_token_extensions::account_frozen)]
+    Frozen {
+        reason: u8,
+    },
+    Overflow,
+    Paused = 7,
+    Closed,
+}
+
+#[derive(SplInterfaceInstruction)]
+pub enum SampleTokenView {
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u64 },
+    #[interface(srfc20_token::transfer)]
+    Transfer { amount: u64 },
+    #[interface(example_token_extensions::balance_of)]
+    BalanceOf { owner: Pubkey },
+    #[interface(srfc23_token_metadata::create_metadata)]
+    CreateMetadata {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    #[interface(example_token_extensions::get_metadata)]
+    GetMetadata,
+}
+
 #[test]
 fn test_compiles() {}
+
+#[test]
+fn test_required_interface_missing() {
+    let declared = vec![
+        InterfaceInstruction {
+            interface_namespace: "srfc22_associated_token".to_string(),
+            interface_version: 1,
+            instruction_namespace: "freeze".to_string(),
+            required_args: vec![],
+            optional: false,
+            returns: vec![],
+        },
+        InterfaceInstruction {
+            interface_namespace: "srfc22_associated_token".to_string(),
+            interface_version: 1,
+            instruction_namespace: "thaw".to_string(),
+            required_args: vec![],
+            optional: false,
+            returns: vec![],
+        },
+    ];
+    assert_eq!(
+        evaluate_interface_instructions(declared),
+        Err(error::SplInterfaceError::RequiredInterfaceMissing)
+    );
+}
+
+#[test]
+fn test_optional_instruction_omitted() {
+    let string_args = || {
+        vec![
+            ("name".to_string(), RequiredArgType::String),
+            ("symbol".to_string(), RequiredArgType::String),
+            ("uri".to_string(), RequiredArgType::String),
+        ]
+    };
+    let declared = vec![
+        InterfaceInstruction {
+            interface_namespace: "srfc20_token".to_string(),
+            interface_version: 1,
+            instruction_namespace: "mint_to".to_string(),
+            required_args: vec![("amount".to_string(), RequiredArgType::U64)],
+            optional: false,
+            returns: vec![],
+        },
+        InterfaceInstruction {
+            interface_namespace: "srfc20_token".to_string(),
+            interface_version: 1,
+            instruction_namespace: "transfer".to_string(),
+            required_args: vec![("amount".to_string(), RequiredArgType::U64)],
+            optional: false,
+            returns: vec![],
+        },
+        InterfaceInstruction {
+            interface_namespace: "srfc23_token_metadata".to_string(),
+            interface_version: 1,
+            instruction_namespace: "create_metadata".to_string(),
+            required_args: string_args(),
+            optional: false,
+            returns: vec![],
+        },
+    ];
+    let manifest = evaluate_interface_instructions(declared).unwrap();
+    let metadata = manifest.interface("srfc23_token_metadata", 1).unwrap();
+    assert_eq!(
+        metadata.instruction("create_metadata").unwrap().status,
+        manifest::ManifestInstructionStatus::Implemented
+    );
+    let update = metadata.instruction("update_metadata").unwrap();
+    assert!(update.optional);
+    assert_eq!(update.status, manifest::ManifestInstructionStatus::Omitted);
+}
+
+#[test]
+fn test_versioned_interface_claims_base_version() {
+    let declared = instructions::SRFC20V2::instructions();
+    let manifest = evaluate_interface_instructions(declared).unwrap();
+    assert_eq!(manifest.versions("srfc20_token"), vec![1, 2]);
+    // Stable discriminators are shared between both versions
+    let v1 = manifest.interface("srfc20_token", 1).unwrap();
+    let v2 = manifest.interface("srfc20_token", 2).unwrap();
+    assert_eq!(
+        v1.instruction("transfer").unwrap().discriminator,
+        v2.instruction("transfer").unwrap().discriminator
+    );
+}
+
+#[test]
+fn test_versioned_interfaces_extend_base() {
+    for interface in registered_interfaces() {
+        if let Some(base) = interface.extends {
+            let base = registered_interface(&qualified_namespace(interface.namespace, base))
+                .expect("Extended version must be registered");
+            let extended = (interface.instructions)();
+            for ix in (base.instructions)() {
+                assert!(extended.iter().any(|e| {
+                    e.instruction_namespace == ix.instruction_namespace
+                        && e.required_args == ix.required_args
+                }));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pack_unpack() {
+    let ix = SampleTokenD::Custom {
+        custom_arg_1: Pubkey::new_unique(),
+        custom_arg_2: 42,
+    };
+    let mut buf = vec![];
+    ix.pack(&mut buf).unwrap();
+    assert_eq!(&buf[..8], &custom_discriminator("custom"));
+    match SampleTokenD::unpack(&buf).unwrap() {
+        SampleTokenD::Custom {
+            custom_arg_1,
+            custom_arg_2,
+        } => {
+            assert_eq!(buf[8..40], custom_arg_1.to_bytes());
+            assert_eq!(custom_arg_2, 42);
+        }
+        _ => panic!("Unpacked the wrong instruction"),
+    }
+
+    let mut buf = vec![];
+    SampleSoulboundToken::MintTo { amount: 7 }
+        .pack(&mut buf)
+        .unwrap();
+    assert_eq!(
+        &buf[..8],
+        &instructions::SRFC20::instructions()[0].discriminator()
+    );
+    assert_eq!(
+        SampleSoulboundToken::unpack(&buf),
+        Ok(SampleSoulboundToken::MintTo { amount: 7 })
+    );
+}
+
+#[test]
+fn test_unimplemented_instruction_unsupported() {
+    let transfer = UnimplementedInstruction {
+        interface_namespace: "srfc20_token".to_string(),
+        interface_version: 1,
+        instruction_namespace: "transfer".to_string(),
+    };
+    let mut buf = transfer.discriminator().to_vec();
+    buf.extend_from_slice(&7u64.to_le_bytes());
+    assert_eq!(
+        SampleSoulboundToken::unpack(&buf),
+        Err(ProgramError::from(
+            error::InterfaceProgramError::UnsupportedInterfaceInstruction
+        ))
+    );
+
+    let declared = vec![InterfaceInstruction {
+        interface_namespace: "srfc20_token".to_string(),
+        interface_version: 1,
+        instruction_namespace: "mint_to".to_string(),
+        required_args: vec![("amount".to_string(), RequiredArgType::U64)],
+        optional: false,
+        returns: vec![],
+    }];
+    let manifest = evaluate_program_interfaces(declared, vec![transfer], None).unwrap();
+    assert_eq!(
+        manifest
+            .interface("srfc20_token", 1)
+            .unwrap()
+            .instruction("transfer")
+            .unwrap()
+            .status,
+        manifest::ManifestInstructionStatus::Unsupported
+    );
+}
+
+#[test]
+fn test_anchor_module_unimplemented_instruction() {
+    let mut program: syn::ItemMod = syn::parse_quote! {
+        #[interface_unimplemented(srfc20_token::transfer)]
+        pub mod soulbound_token {
+            use super::*;
+
+            #[interface(srfc20_token::mint_to)]
+            pub fn mint_to(ctx: Context<MintTo>, amount: u64) -> Result<()> {
+                Ok(())
+            }
+        }
+    };
+    let manifest = process_module(&program).unwrap();
+    let srfc20 = manifest.interface("srfc20_token", 1).unwrap();
+    assert_eq!(
+        srfc20.instruction("mint_to").unwrap().status,
+        manifest::ManifestInstructionStatus::Implemented
+    );
+    assert_eq!(
+        srfc20.instruction("transfer").unwrap().status,
+        manifest::ManifestInstructionStatus::Unsupported
+    );
+    // Without the annotation, the missing instruction fails evaluation
+    program.attrs.clear();
+    assert_eq!(
+        process_module(&program),
+        Err(error::SplInterfaceError::InstructionMissing)
+    );
+}
+
+#[test]
+fn test_implemented_interfaces_checked_both_ways() {
+    let implements = |namespaces: &[&str]| {
+        Some(
+            namespaces
+                .iter()
+                .map(|namespace| ImplementedInterface {
+                    namespace: namespace.to_string(),
+                    version: 1,
+                })
+                .collect::<Vec<_>>(),
+        )
+    };
+    // Declared, but none of its instructions are annotated
+    assert_eq!(
+        evaluate_program_interfaces(
+            instructions::SRFC20::instructions(),
+            vec![],
+            implements(&["srfc20_token", "srfc21_token"]),
+        ),
+        Err(error::SplInterfaceError::DeclaredInterfaceNotImplemented)
+    );
+    // Annotated, but not declared
+    let mut declared = instructions::SRFC20::instructions();
+    declared.extend(instructions::SRFC21::instructions());
+    assert_eq!(
+        evaluate_program_interfaces(declared.clone(), vec![], implements(&["srfc20_token"])),
+        Err(error::SplInterfaceError::UndeclaredInterface)
+    );
+    assert!(evaluate_program_interfaces(
+        declared,
+        vec![],
+        implements(&["srfc20_token", "srfc21_token"])
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_anchor_module_implemented_interfaces() {
+    let program = |implements: syn::Attribute| -> syn::ItemMod {
+        syn::parse_quote! {
+            #implements
+            pub mod token {
+                use super::*;
+
+                #[interface(srfc20_token::mint_to)]
+                pub fn mint_to(ctx: Context<MintTo>, amount: u64) -> Result<()> {
+                    Ok(())
+                }
+
+                #[interface(srfc20_token::transfer)]
+                pub fn transfer(ctx: Context<Transfer>, amount: u64) -> Result<()> {
+                    Ok(())
+                }
+
+                #[interface(srfc21_token::burn)]
+                pub fn burn(ctx: Context<Burn>, amount: u64) -> Result<()> {
+                    Ok(())
+                }
+            }
+        }
+    };
+    assert!(process_module(&program(syn::parse_quote!(
+        #[implements(srfc20_token, srfc21_token)]
+    )))
+    .is_ok());
+    // Declared, but none of its instructions are annotated
+    assert_eq!(
+        process_module(&program(syn::parse_quote!(
+            #[implements(srfc20_token, srfc21_token, srfc22_associated_token)]
+        ))),
+        Err(error::SplInterfaceError::DeclaredInterfaceNotImplemented)
+    );
+    // Annotated, but not declared
+    assert_eq!(
+        process_module(&program(syn::parse_quote!(#[implements(srfc20_token)]))),
+        Err(error::SplInterfaceError::UndeclaredInterface)
+    );
+}
+
+#[test]
+fn test_anchor_module_implements_marker_traits() {
+    let program: syn::ItemMod = syn::parse_quote! {
+        #[interface_unimplemented(srfc20_token::transfer)]
+        pub mod soulbound_token {
+            use super::*;
+
+            #[interface(srfc20_token::mint_to)]
+            pub fn mint_to(ctx: Context<MintTo>, amount: u64) -> Result<()> {
+                Ok(())
+            }
+
+            #[interface(srfc21_token::burn)]
+            pub fn burn(ctx: Context<Burn>, amount: u64) -> Result<()> {
+                Ok(())
+            }
+        }
+    };
+    let impls: syn::File = syn::parse2(process_module_implements(&program).unwrap()).unwrap();
+    let implemented: Vec<(String, String)> = impls
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Impl(item_impl) => Some(item_impl),
+            _ => None,
+        })
+        .map(|item_impl| {
+            let (_, trait_path, _) = item_impl.trait_.as_ref().unwrap();
+            let self_ty = &item_impl.self_ty;
+            (
+                trait_path.to_token_stream().to_string().replace(' ', ""),
+                self_ty.to_token_stream().to_string().replace(' ', ""),
+            )
+        })
+        .collect();
+    // `transfer` is unsupported, so only sRFC 21 is fully implemented
+    assert_eq!(
+        implemented,
+        vec![(
+            "spl_interface_instructions::Implements<spl_interface_instructions::instructions::SRFC21>"
+                .to_string(),
+            "crate::program::SoulboundToken".to_string(),
+        )]
+    );
+}
+
+#[test]
+fn test_trailing_args_policy() {
+    let freeze = |required_args| InterfaceInstruction {
+        interface_namespace: "srfc22_associated_token".to_string(),
+        interface_version: 1,
+        instruction_namespace: "freeze".to_string(),
+        required_args,
+        optional: false,
+        returns: vec![],
+    };
+    let mut declared = instructions::SRFC20::instructions();
+    declared.push(freeze(vec![("memo".to_string(), RequiredArgType::String)]));
+    assert_eq!(
+        evaluate_interface_instructions(declared),
+        Err(error::SplInterfaceError::MissingArgument)
+    );
+}
+
+#[test]
+fn test_tuple_and_struct_wrapped_args() {
+    assert_eq!(MintToArgs::ARGS, &[("amount", "u64")] as &[(&str, &str)]);
+    assert!(args_match(MintToArgs::ARGS, &[("amount", "u64")], false));
+    assert!(!args_match(MintToArgs::ARGS, &[("amount", "u32")], false));
+    assert!(!args_match(
+        &[("amount", "u64"), ("memo", "String")],
+        &[("amount", "u64")],
+        false
+    ));
+    assert!(args_match(
+        &[("amount", "u64"), ("memo", "String")],
+        &[("amount", "u64")],
+        true
+    ));
+    assert_eq!(BurnArgs::ARGS, &[("", "u64")] as &[(&str, &str)]);
+    assert!(args_match(BurnArgs::ARGS, &[("amount", "u64")], false));
+    assert!(!args_match(BurnArgs::ARGS, &[("amount", "u32")], false));
+
+    for (ix, interface_ix) in [
+        (
+            SampleTokenK::MintTo(MintToArgs { amount: 5 }),
+            &instructions::SRFC20::instructions()[0],
+        ),
+        (
+            SampleTokenK::Transfer(9),
+            &instructions::SRFC20::instructions()[1],
+        ),
+        (
+            SampleTokenK::Burn(BurnArgs(3)),
+            &instructions::SRFC21::instructions()[0],
+        ),
+    ] {
+        let mut buf = vec![];
+        ix.pack(&mut buf).unwrap();
+        assert_eq!(&buf[..8], &interface_ix.discriminator());
+        assert_eq!(SampleTokenK::unpack(&buf), Ok(ix));
+    }
+}
+
+#[test]
+fn test_key_value_annotation() {
+    let mut buf = vec![];
+    SampleTokenL::Approve { amount: 1 }.pack(&mut buf).unwrap();
+    assert_eq!(
+        &buf[..8],
+        &instructions::SRFC20V2::instructions()[2].discriminator()
+    );
+}
+
+#[test]
+fn test_multiple_annotations_per_variant() {
+    let burn = &instructions::SRFC21::instructions()[0];
+    let approve = &instructions::SRFC20V2::instructions()[2];
+    for interface_ix in [burn, approve] {
+        let mut buf = interface_ix.discriminator().to_vec();
+        buf.extend_from_slice(&3u64.to_le_bytes());
+        assert_eq!(
+            SampleTokenM::unpack(&buf),
+            Ok(SampleTokenM::Burn { amount: 3 })
+        );
+    }
+    let mut buf = vec![];
+    SampleTokenM::Burn { amount: 3 }.pack(&mut buf).unwrap();
+    assert_eq!(&buf[..8], &burn.discriminator());
+}
+
+#[test]
+fn test_legacy_discriminators() {
+    // Legacy clients send the borsh variant tag
+    let legacy = SampleLegacyToken::Transfer { amount: 11 }
+        .try_to_vec()
+        .unwrap();
+    assert_eq!(legacy[0], 1);
+    assert_eq!(
+        SampleLegacyToken::unpack(&legacy),
+        Ok(SampleLegacyToken::Transfer { amount: 11 })
+    );
+    assert_eq!(
+        SampleLegacyToken::unpack(&[2]),
+        Ok(SampleLegacyToken::Pause)
+    );
+    assert_eq!(
+        SampleLegacyToken::unpack(&[3]),
+        Err(ProgramError::InvalidInstructionData)
+    );
+
+    // Migrated clients send the interface discriminator
+    let mut buf = vec![];
+    SampleLegacyToken::Transfer { amount: 11 }
+        .pack(&mut buf)
+        .unwrap();
+    assert_eq!(
+        &buf[..8],
+        &instructions::SRFC20::instructions()[1].discriminator()
+    );
+    assert_eq!(
+        SampleLegacyToken::unpack(&buf),
+        Ok(SampleLegacyToken::Transfer { amount: 11 })
+    );
+}
+
+#[test]
+fn test_interface_account() {
+    let account = SampleTokenAccount {
+        mint: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        amount: 100,
+        is_frozen: false,
+        delegate: Some(Pubkey::new_unique()),
+    };
+    let mut data = vec![];
+    account.pack(&mut data).unwrap();
+    let interface_account = &instructions::SRFC20::accounts()[0];
+    assert_eq!(
+        interface_account.discriminator_preimage(),
+        "srfc20_token:account:TokenAccount"
+    );
+    assert_eq!(&data[..8], &interface_account.discriminator());
+    assert_eq!(
+        SampleTokenAccount::DISCRIMINATOR,
+        interface_account.discriminator()
+    );
+    // Accounts may be allocated with room to grow
+    data.resize(data.len() + 32, 0);
+    assert_eq!(SampleTokenAccount::try_from_slice(&data), Ok(account));
+
+    data[0] ^= 1;
+    assert_eq!(
+        SampleTokenAccount::try_from_slice(&data),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
+
+#[test]
+fn test_interface_event_logs() {
+    let program_id = Pubkey::new_unique();
+    let other_program_id = Pubkey::new_unique();
+    let event = SampleTransferEvent {
+        from: Pubkey::new_unique(),
+        to: Pubkey::new_unique(),
+        amount: 9,
+        memo: b"invoice 42".to_vec(),
+    };
+    let mut data = vec![];
+    event.pack(&mut data).unwrap();
+    assert_eq!(
+        &data[..8],
+        &instructions::SRFC20::events()[0].discriminator()
+    );
+    event.emit().unwrap();
+
+    let program_data = format!(
+        "Program data: {}",
+        base64::engine::general_purpose::STANDARD.encode(&data)
+    );
+    let logs: Vec<String> = vec![
+        format!("Program {} invoke [1]", program_id),
+        program_data.clone(),
+        format!("Program {} invoke [2]", other_program_id),
+        program_data,
+        format!("Program {} success", other_program_id),
+        "Program data: bm90IGFuIGV2ZW50".to_string(),
+        format!("Program {} success", program_id),
+    ];
+    assert_eq!(
+        events::parse_events::<SampleTransferEvent>(&logs, None),
+        vec![event.clone(), event]
+    );
+    assert_eq!(
+        events::parse_events::<SampleTransferEvent>(&logs, Some(&other_program_id)).len(),
+        1
+    );
+}
+
+#[test]
+fn test_interface_error_codes() {
+    let insufficient_funds = &instructions::ExampleTokenExtensions::errors()[0];
+    assert_eq!(
+        ProgramError::from(SampleTokenError::InsufficientFunds),
+        ProgramError::Custom(insufficient_funds.code())
+    );
+    assert_eq!(
+        interface_errors::decode_program_error(&SampleTokenError::InsufficientFunds.into()),
+        Some(insufficient_funds.clone())
+    );
+    let frozen =
+        interface_errors::decode_error_code(SampleTokenError::Frozen { reason: 1 }.error_code())
+            .unwrap();
+    assert_eq!(frozen.interface_namespace, "example_token_extensions");
+    assert_eq!(frozen.interface_version, 1);
+    assert_eq!(frozen.error_namespace, "account_frozen");
+    // Unmapped variants keep their own discriminants
+    assert_eq!(SampleTokenError::Overflow.error_code(), 2);
+    assert_eq!(SampleTokenError::Paused.error_code(), 7);
+    assert_eq!(SampleTokenError::Closed.error_code(), 8);
+    assert_eq!(interface_errors::decode_error_code(2), None);
+    // The accepted sRFCs don't define errors
+    assert!(instructions::SRFC20::errors().is_empty());
+}
+
+#[test]
+fn test_view_return_data() {
+    assert_eq!(SampleTokenView::set_balance_of_return_data(&42), Ok(()));
+    let metadata = ("Token".to_string(), "TKN".to_string(), "uri".to_string());
+    assert_eq!(
+        SampleTokenView::set_get_metadata_return_data(&metadata),
+        Ok(())
+    );
+    let program_id = Pubkey::new_unique();
+    let data = metadata.try_to_vec().unwrap();
+    assert_eq!(
+        views::decode_return_data(&program_id, Some((program_id, data.clone()))),
+        Some(metadata)
+    );
+    // Data returned by another program is ignored
+    assert_eq!(
+        views::decode_return_data::<(String, String, String)>(
+            &program_id,
+            Some((Pubkey::new_unique(), data))
+        ),
+        None
+    );
+    assert_eq!(views::decode_return_data::<u64>(&program_id, None), None);
+}
+
+/// Only compiles for programs that fully implement `I`
+fn assert_implements<P: Implements<I>, I: Interface>() {}
+
+#[test]
+fn test_implements_marker_traits() {
+    assert_implements::<SampleTokenB, instructions::SRFC20>();
+    assert_implements::<SampleTokenB, instructions::SRFC22>();
+    assert_implements::<SampleTokenI, instructions::SRFC21>();
+    assert_implements::<SampleTokenL, instructions::SRFC20V2>();
+    assert_implements::<SampleTokenM, instructions::SRFC20>();
+    assert_implements::<SampleTokenM, instructions::SRFC20V2>();
+    assert_implements::<SampleTokenView, instructions::SRFC23>();
+    assert_implements::<SampleTokenView, instructions::ExampleTokenExtensions>();
+}
+
+#[test]
+fn test_embedded_manifest() {
+    let manifest =
+        embedded::decode_manifest(&SPL_INTERFACE_MANIFEST_SAMPLE_SOULBOUND_TOKEN).unwrap();
+    let srfc20 = manifest.interface("srfc20_token", 1).unwrap();
+    assert_eq!(
+        srfc20.instruction("mint_to").unwrap().discriminator,
+        instructions::SRFC20::instructions()[0].discriminator()
+    );
+    assert_eq!(
+        srfc20.instruction("transfer").unwrap().status,
+        manifest::ManifestInstructionStatus::Unsupported
+    );
+    // Manifests are found anywhere in a binary
+    let mut binary = vec![0x7f, b'E', b'L', b'F', 0, 0];
+    binary.extend_from_slice(&SPL_INTERFACE_MANIFEST_SAMPLE_SOULBOUND_TOKEN);
+    binary.extend_from_slice(&[0; 16]);
+    binary.extend_from_slice(&SPL_INTERFACE_MANIFEST_SAMPLE_TOKEN_I);
+    let manifests = embedded::read_embedded_manifests(&binary).unwrap();
+    assert_eq!(manifests.len(), 2);
+    assert_eq!(manifests[0], manifest);
+    assert_eq!(manifests[1].versions("srfc21_token"), vec![1]);
+    // Truncated manifests are skipped
+    assert_eq!(
+        embedded::read_embedded_manifests(&binary[..binary.len() - 8]),
+        Ok(vec![manifest.clone()])
+    );
+    assert_eq!(
+        embedded::read_embedded_manifests(&SPL_INTERFACE_MANIFEST_SAMPLE_TOKEN_I[..64]),
+        Err(embedded::EmbeddedManifestError::Malformed)
+    );
+    assert_eq!(
+        embedded::read_embedded_manifests(&[0; 64]),
+        Err(embedded::EmbeddedManifestError::NotFound)
+    );
+    // Lengths are `u8`-prefixed, so longer namespaces can't be encoded
+    let mut too_large = manifest.clone();
+    too_large.interfaces[0].namespace = "a".repeat(256);
+    assert_eq!(
+        embedded::encode_manifest(&too_large),
+        Err(embedded::EmbeddedManifestError::TooLarge)
+    );
+    assert_eq!(
+        embedded::encode_manifest(&manifest).as_deref(),
+        Ok(&SPL_INTERFACE_MANIFEST_SAMPLE_SOULBOUND_TOKEN[..])
+    );
+}
+
+#[test]
+fn test_find_preceding_interface_ix() {
+    use solana_program::{
+        account_info::AccountInfo,
+        sysvar::instructions::{
+            construct_instructions_data, store_current_index, BorrowedAccountMeta,
+            BorrowedInstruction,
+        },
+    };
+
+    let transfer = |amount: u64| {
+        let mut data = vec![];
+        SampleTokenA::Transfer { amount }.pack(&mut data).unwrap();
+        data
+    };
+    let (token_a, token_b, vault, other) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let account = |pubkey| BorrowedAccountMeta {
+        pubkey,
+        is_signer: false,
+        is_writable: false,
+    };
+    let (first, second, escrow) = (transfer(5), transfer(10), vec![0; 8]);
+    let mut data = construct_instructions_data(&[
+        BorrowedInstruction {
+            program_id: &token_a,
+            accounts: vec![account(&other), account(&vault)],
+            data: &first,
+        },
+        BorrowedInstruction {
+            program_id: &token_b,
+            accounts: vec![account(&other), account(&other)],
+            data: &second,
+        },
+        BorrowedInstruction {
+            program_id: &other,
+            accounts: vec![],
+            data: &escrow,
+        },
+    ]);
+    store_current_index(&mut data, 2);
+    let key = solana_program::sysvar::instructions::id();
+    let mut lamports = 0;
+    let owner = solana_program::sysvar::id();
+    let sysvar = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+
+    let tokens = [token_a, token_b];
+    let transfers = introspection::find_preceding_interface_ixs::<instructions::SRFC20>(
+        "transfer", &tokens, &sysvar,
+    )
+    .unwrap();
+    assert_eq!(transfers.len(), 2);
+    assert_eq!(transfers[0].instruction.program_id, token_a);
+    assert_eq!(transfers[0].account("recipient").unwrap().pubkey, vault);
+    assert_eq!(transfers[0].args::<u64>(), Ok(5));
+    let closest = introspection::find_preceding_interface_ix::<instructions::SRFC20>(
+        "transfer", &tokens, &sysvar,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!((closest.index, closest.args::<u64>()), (1, Ok(10)));
+    // Instructions sent to other programs are ignored
+    let closest = introspection::find_preceding_interface_ix::<instructions::SRFC20>(
+        "transfer",
+        &[token_a],
+        &sysvar,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(closest.index, 0);
+    assert_eq!(
+        introspection::find_preceding_interface_ix::<instructions::SRFC20>(
+            "transfer",
+            &[other],
+            &sysvar
+        ),
+        Ok(None)
+    );
+    assert_eq!(
+        introspection::find_preceding_interface_ix::<instructions::SRFC20>(
+            "mint_to", &tokens, &sysvar
+        ),
+        Ok(None)
+    );
+    assert_eq!(
+        introspection::find_preceding_interface_ix::<instructions::SRFC20>(
+            "approve", &tokens, &sysvar
+        ),
+        Err(error::InterfaceProgramError::UnknownInterfaceInstruction.into())
+    );
+}
+
+#[test]
+fn test_lockfile_detects_wire_format_changes() {
+    use lockfile::{
+        diff_lock_entries, parse_lockfile, render_lockfile, update_lock_entries, LockChange,
+    };
+
+    let locked_enum: syn::ItemEnum = syn::parse_quote! {
+        pub enum LockedInstruction {
+            #[interface(srfc20_token::mint_to)]
+            MintTo { amount: u64 },
+            #[interface(srfc20_token::transfer)]
+            Transfer(u64, Option<Pubkey>),
+            Configure { fee: u32, recipient: Pubkey },
+        }
+    };
+    let entries = instruction_lock_entries(&locked_enum).unwrap();
+    let rendered = render_lockfile(&entries);
+    assert!(rendered.contains(&format!(
+        "LockedInstruction::Transfer {} (u64, Option<Pubkey>)",
+        instructions::SRFC20::instructions()[1]
+            .discriminator()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+    )));
+    let locked = parse_lockfile(&rendered).unwrap();
+    assert_eq!(locked, entries);
+
+    let diff = |item_enum: syn::ItemEnum| {
+        diff_lock_entries(
+            &locked,
+            "LockedInstruction",
+            &instruction_lock_entries(&item_enum).unwrap(),
+        )
+    };
+    let changed_keys = |changes: Vec<LockChange>| -> Vec<String> {
+        changes
+            .iter()
+            .map(|change| match change {
+                LockChange::Changed { current, .. } => current.key(),
+                _ => panic!("Expected a changed entry: {}", change),
+            })
+            .collect()
+    };
+    // Renaming the annotation, reordering fields and widening a type
+    // all break the wire format
+    assert_eq!(
+        changed_keys(diff(syn::parse_quote! {
+            pub enum LockedInstruction {
+                #[interface(srfc21_token::burn)]
+                MintTo { amount: u64 },
+                #[interface(srfc20_token::transfer)]
+                Transfer(Option<Pubkey>, u64),
+                Configure { fee: u64, recipient: Pubkey },
+            }
+        })),
+        vec![
+            "LockedInstruction::MintTo",
+            "LockedInstruction::Transfer",
+            "LockedInstruction::Configure"
+        ]
+    );
+    // Module paths don't change the layout
+    assert!(diff(syn::parse_quote! {
+        pub enum LockedInstruction {
+            #[interface(srfc20_token::mint_to)]
+            MintTo { amount: u64 },
+            #[interface(srfc20_token::transfer)]
+            Transfer(u64, Option<solana_program::pubkey::Pubkey>),
+            Configure { fee: u32, recipient: ::solana_program::pubkey::Pubkey },
+        }
+    })
+    .is_empty());
+    assert_eq!(
+        lockfile::normalize_layout("{ a: std::vec::Vec<crate::state::Entry>, b: [u8; 32] }"),
+        "{ a: Vec<Entry>, b: [u8; 32] }"
+    );
+    // So do removing and adding variants, but not reordering them,
+    // although added variants aren't breaking
+    let changes = diff(syn::parse_quote! {
+        pub enum LockedInstruction {
+            Configure { fee: u32, recipient: Pubkey },
+            #[interface(srfc20_token::mint_to)]
+            MintTo { amount: u64 },
+            Close,
+        }
+    });
+    assert_eq!(changes.len(), 2);
+    assert!(
+        matches!(&changes[0], LockChange::Added(entry) if entry.key() == "LockedInstruction::Close")
+    );
+    assert!(!changes[0].is_breaking() && changes[1].is_breaking());
+    assert!(
+        matches!(&changes[1], LockChange::Removed(entry) if entry.key() == "LockedInstruction::Transfer")
+    );
+
+    // Updating an item keeps the entries of others
+    let other: syn::ItemEnum = syn::parse_quote! {
+        pub enum OtherInstruction {
+            Ping,
+        }
+    };
+    let updated = update_lock_entries(
+        [locked.clone(), instruction_lock_entries(&other).unwrap()].concat(),
+        "LockedInstruction",
+        entries[..1].to_vec(),
+    );
+    assert_eq!(
+        updated.iter().map(|entry| entry.key()).collect::<Vec<_>>(),
+        vec!["LockedInstruction::MintTo", "OtherInstruction::Ping"]
+    );
+    assert!(parse_lockfile("LockedInstruction::MintTo 0102 { amount: u64 }").is_err());
+}
+
+#[test]
+fn test_diff_manifests() {
+    use manifest::{diff_manifests, ManifestChange};
+
+    let old = evaluate_interface_instructions(instructions::SRFC20::instructions()).unwrap();
+    assert!(diff_manifests(&old, &old).is_empty());
+
+    // Implementing an optional instruction or another interface is
+    // compatible
+    let mut declared = instructions::SRFC20::instructions();
+    declared.extend(
+        instructions::SRFC23::instructions()
+            .into_iter()
+            .filter(|ix| !ix.optional),
+    );
+    let metadata = evaluate_interface_instructions(declared.clone()).unwrap();
+    declared.extend(
+        instructions::SRFC23::instructions()
+            .into_iter()
+            .filter(|ix| ix.optional),
+    );
+    declared.extend(instructions::SRFC21::instructions());
+    let changes = diff_manifests(
+        &metadata,
+        &evaluate_interface_instructions(declared).unwrap(),
+    );
+    assert_eq!(
+        changes,
+        vec![
+            ManifestChange::InstructionAdded {
+                interface: "srfc23_token_metadata".to_string(),
+                instruction: "update_metadata".to_string(),
+            },
+            ManifestChange::InterfaceAdded {
+                interface: "srfc21_token".to_string(),
+            },
+        ]
+    );
+    assert!(changes.iter().all(|change| !change.is_breaking()));
+
+    // Dropping an instruction or changing its discriminator is breaking
+    let soulbound =
+        embedded::decode_manifest(&SPL_INTERFACE_MANIFEST_SAMPLE_SOULBOUND_TOKEN).unwrap();
+    let changes = diff_manifests(&old, &soulbound);
+    assert!(changes.contains(&ManifestChange::InstructionRemoved {
+        interface: "srfc20_token".to_string(),
+        instruction: "transfer".to_string(),
+    }));
+    let mut changed = old.clone();
+    changed.interfaces[0].instructions[0].discriminator = [0; 8];
+    let changes = diff_manifests(&old, &changed);
+    assert_eq!(changes.len(), 1);
+    assert!(matches!(
+        &changes[0],
+        ManifestChange::DiscriminatorChanged { instruction, .. } if instruction == "mint_to"
+    ));
+    assert!(changes[0].is_breaking());
+
+    // Moving to a version that keeps the discriminators is compatible,
+    // while dropping the interface altogether is not
+    let mut v2 = evaluate_interface_instructions(instructions::SRFC20V2::instructions()).unwrap();
+    v2.interfaces.retain(|interface| interface.version == 2);
+    let changes = diff_manifests(&old, &v2);
+    assert_eq!(
+        changes[0],
+        ManifestChange::InterfaceRemoved {
+            interface: "srfc20_token".to_string(),
+            superseded_by: Some("srfc20_token@2".to_string()),
+        }
+    );
+    assert!(changes.iter().all(|change| !change.is_breaking()));
+    let changes = diff_manifests(&old, &manifest::InterfaceManifest::default());
+    assert!(changes[0].is_breaking());
+
+    // Manifests of several instruction enums merge into one
+    let mut merged = soulbound.clone();
+    merged.merge(old.clone());
+    assert!(diff_manifests(&old, &merged).is_empty());
+}
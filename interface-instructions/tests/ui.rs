@@ -0,0 +1,23 @@
+use std::path::Path;
+
+/// Pins the compile errors reported for malformed annotations and
+/// breaking changes to a locked wire format
+#[test]
+fn test_malformed_annotations() {
+    // The derive reads `interfaces.lock` from the crate being compiled,
+    // which for these cases is the project trybuild generates under
+    // `<target>/tests/trybuild/<crate>`
+    let project_dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .parent()
+        .unwrap()
+        .join("tests/trybuild")
+        .join(env!("CARGO_PKG_NAME"));
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::copy(
+        "tests/ui/interfaces.lock",
+        project_dir.join("interfaces.lock"),
+    )
+    .unwrap();
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
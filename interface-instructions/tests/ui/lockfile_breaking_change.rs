@@ -0,0 +1,10 @@
+use spl_interface_instructions::*;
+
+// `tests/ui/interfaces.lock` locks `amount` as a `u64`
+#[derive(SplInterfaceInstruction)]
+pub enum LockedToken {
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u32 },
+}
+
+fn main() {}
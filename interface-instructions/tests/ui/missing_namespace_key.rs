@@ -0,0 +1,9 @@
+use spl_interface_instructions::*;
+
+#[derive(SplInterfaceInstruction)]
+pub enum SampleToken {
+    #[interface(instruction = "mint_to")]
+    MintTo { amount: u64 },
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+//! Errors returned by the interface registry program
+
+use solana_program::program_error::ProgramError;
+
+/// Errors returned by the interface registry program
+#[derive(Clone, Copy, Debug, Eq, thiserror::Error, PartialEq)]
+pub enum RegistryError {
+    #[error("Registry account does not match the program's address")]
+    InvalidRegistryAddress,
+    #[error("Program is not owned by the upgradeable loader")]
+    ProgramNotUpgradeable,
+    #[error("Program data account does not belong to the program")]
+    InvalidProgramData,
+    #[error("Signer is not the program's upgrade authority")]
+    InvalidUpgradeAuthority,
+    #[error("Interface version is already registered")]
+    AlreadyRegistered,
+    #[error("Interface version is not registered")]
+    NotRegistered,
+}
+
+impl From<RegistryError> for ProgramError {
+    fn from(e: RegistryError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,97 @@
+//! Instructions of the interface registry program, along with their
+//! builders
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+use spl_interface_instructions::*;
+
+use crate::{find_registry_address, id};
+
+/// Instructions of the interface registry program
+///
+/// Both instructions expect the following accounts:
+///
+/// 0. `[writable]` The program's registry account
+/// 1. `[]` The program
+/// 2. `[]` The program's program data account
+/// 3. `[signer]` The program's upgrade authority
+/// 4. `[writable, signer]` The payer covering any change in rent
+/// 5. `[]` The system program
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, SplInterfaceInstruction)]
+pub enum RegistryInstruction {
+    /// Registers an interface version the program implements, creating
+    /// the registry account if needed
+    RegisterInterface { namespace: String, version: u8 },
+    /// Unregisters an interface version the program no longer
+    /// implements
+    UnregisterInterface { namespace: String, version: u8 },
+}
+
+/// Creates a `RegisterInterface` instruction
+pub fn register_interface(
+    program_id: &Pubkey,
+    upgrade_authority: &Pubkey,
+    payer: &Pubkey,
+    namespace: &str,
+    version: u8,
+) -> Instruction {
+    registry_instruction(
+        program_id,
+        upgrade_authority,
+        payer,
+        RegistryInstruction::RegisterInterface {
+            namespace: namespace.to_string(),
+            version,
+        },
+    )
+}
+
+/// Creates an `UnregisterInterface` instruction
+pub fn unregister_interface(
+    program_id: &Pubkey,
+    upgrade_authority: &Pubkey,
+    payer: &Pubkey,
+    namespace: &str,
+    version: u8,
+) -> Instruction {
+    registry_instruction(
+        program_id,
+        upgrade_authority,
+        payer,
+        RegistryInstruction::UnregisterInterface {
+            namespace: namespace.to_string(),
+            version,
+        },
+    )
+}
+
+fn registry_instruction(
+    program_id: &Pubkey,
+    upgrade_authority: &Pubkey,
+    payer: &Pubkey,
+    instruction: RegistryInstruction,
+) -> Instruction {
+    let (programdata_address, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    let mut data = vec![];
+    instruction
+        .pack(&mut data)
+        .expect("Instruction data is always serializable");
+    Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new(find_registry_address(program_id).0, false),
+            AccountMeta::new_readonly(*program_id, false),
+            AccountMeta::new_readonly(programdata_address, false),
+            AccountMeta::new_readonly(*upgrade_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data,
+    }
+}
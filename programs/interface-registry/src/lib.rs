@@ -0,0 +1,33 @@
+//! On-chain registry of the interfaces programs implement
+//!
+//! A program's upgrade authority registers each interface version the
+//! program implements in a PDA keyed by the program id, so that
+//! wallets can look up interface support with a single account read
+//! instead of simulating the program
+
+pub mod error;
+pub mod instruction;
+pub mod processor;
+pub mod state;
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
+
+use solana_program::pubkey::Pubkey;
+
+solana_program::declare_id!("A9VgcWReCAGUKkAqpWNwgxYVt9n4YyRZw7ez4qup9GLX");
+
+/// Seed prefix of a program's registry account
+pub const REGISTRY_SEED: &[u8] = b"interfaces";
+
+/// Returns the address of a program's registry account, along with
+/// its bump seed
+pub fn find_registry_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_SEED, program_id.as_ref()], &id())
+}
+
+/// Returns the hash registry entries are keyed by (ie.
+/// `hash("srfc20_token")`)
+pub fn namespace_hash(namespace: &str) -> [u8; 32] {
+    solana_program::hash::hash(namespace.as_bytes()).to_bytes()
+}
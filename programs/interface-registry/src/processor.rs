@@ -0,0 +1,206 @@
+//! Processor for the interface registry program
+
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+use spl_interface_instructions::InterfaceInstructionPack;
+
+use crate::{
+    error::RegistryError, find_registry_address, instruction::RegistryInstruction, namespace_hash,
+    state::InterfaceRegistry, REGISTRY_SEED,
+};
+
+/// Bincode tag of `UpgradeableLoaderState::Program`
+const PROGRAM_TAG: u32 = 2;
+/// Bincode tag of `UpgradeableLoaderState::ProgramData`
+const PROGRAM_DATA_TAG: u32 = 3;
+
+/// Processes a `RegistryInstruction`
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (namespace, version, register) = match RegistryInstruction::unpack(data)? {
+        RegistryInstruction::RegisterInterface { namespace, version } => (namespace, version, true),
+        RegistryInstruction::UnregisterInterface { namespace, version } => {
+            (namespace, version, false)
+        }
+    };
+    let account_info_iter = &mut accounts.iter();
+    let registry_info = next_account_info(account_info_iter)?;
+    let program_info = next_account_info(account_info_iter)?;
+    let programdata_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    check_upgrade_authority(program_info, programdata_info, authority_info)?;
+    let (registry_address, bump) = find_registry_address(program_info.key);
+    if registry_info.key != &registry_address {
+        return Err(RegistryError::InvalidRegistryAddress.into());
+    }
+
+    let mut registry = if registry_info.data_is_empty() {
+        InterfaceRegistry {
+            program_id: *program_info.key,
+            entries: vec![],
+        }
+    } else {
+        if registry_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        InterfaceRegistry::unpack(&registry_info.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?
+    };
+    let hash = namespace_hash(&namespace);
+    if register {
+        msg!("Registering {} version {}", namespace, version);
+        if !registry.register(hash, version) {
+            return Err(RegistryError::AlreadyRegistered.into());
+        }
+    } else {
+        msg!("Unregistering {} version {}", namespace, version);
+        if !registry.unregister(hash, version) {
+            return Err(RegistryError::NotRegistered.into());
+        }
+    }
+
+    let data = registry
+        .try_to_vec()
+        .map_err(|e| ProgramError::BorshIoError(e.to_string()))?;
+    let required_lamports = Rent::get()?.minimum_balance(data.len());
+    if registry_info.data_is_empty() {
+        create_registry_account(
+            program_id,
+            registry_info,
+            payer_info,
+            system_program_info,
+            required_lamports,
+            data.len(),
+            &[REGISTRY_SEED, program_info.key.as_ref(), &[bump]],
+        )?;
+    } else {
+        let lamports = registry_info.lamports();
+        if lamports < required_lamports {
+            invoke(
+                &system_instruction::transfer(
+                    payer_info.key,
+                    registry_info.key,
+                    required_lamports - lamports,
+                ),
+                &[
+                    payer_info.clone(),
+                    registry_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        } else {
+            // Refund the rent freed by a smaller registry
+            let refund = lamports - required_lamports;
+            **registry_info.try_borrow_mut_lamports()? -= refund;
+            **payer_info.try_borrow_mut_lamports()? += refund;
+        }
+        registry_info.realloc(data.len(), false)?;
+    }
+    registry_info.data.borrow_mut().copy_from_slice(&data);
+    Ok(())
+}
+
+/// Creates the registry PDA owned by the program
+///
+/// Anyone can transfer lamports to the PDA before it's created, which
+/// makes `create_account` fail, so a funded PDA is topped up, allocated
+/// and assigned instead (as the associated token account program does)
+fn create_registry_account<'a>(
+    program_id: &Pubkey,
+    registry_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    required_lamports: u64,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let lamports = registry_info.lamports();
+    if lamports == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(
+                payer_info.key,
+                registry_info.key,
+                required_lamports,
+                space as u64,
+                program_id,
+            ),
+            &[
+                payer_info.clone(),
+                registry_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[signer_seeds],
+        );
+    }
+    if lamports < required_lamports {
+        invoke(
+            &system_instruction::transfer(
+                payer_info.key,
+                registry_info.key,
+                required_lamports - lamports,
+            ),
+            &[
+                payer_info.clone(),
+                registry_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(registry_info.key, space as u64),
+        &[registry_info.clone(), system_program_info.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(registry_info.key, program_id),
+        &[registry_info.clone(), system_program_info.clone()],
+        &[signer_seeds],
+    )
+}
+
+/// Checks that `authority_info` signed and is the upgrade authority
+/// of the upgradeable program `program_info`
+fn check_upgrade_authority(
+    program_info: &AccountInfo,
+    programdata_info: &AccountInfo,
+    authority_info: &AccountInfo,
+) -> ProgramResult {
+    if program_info.owner != &bpf_loader_upgradeable::id()
+        || programdata_info.owner != &bpf_loader_upgradeable::id()
+    {
+        return Err(RegistryError::ProgramNotUpgradeable.into());
+    }
+    // `UpgradeableLoaderState::Program { programdata_address }`
+    let program_data = program_info.data.borrow();
+    if program_data.len() < 36 || program_data[..4] != PROGRAM_TAG.to_le_bytes() {
+        return Err(RegistryError::ProgramNotUpgradeable.into());
+    }
+    if program_data[4..36] != programdata_info.key.to_bytes() {
+        return Err(RegistryError::InvalidProgramData.into());
+    }
+    // `UpgradeableLoaderState::ProgramData { slot, upgrade_authority_address }`
+    let programdata = programdata_info.data.borrow();
+    if programdata.len() < 45 || programdata[..4] != PROGRAM_DATA_TAG.to_le_bytes() {
+        return Err(RegistryError::InvalidProgramData.into());
+    }
+    if !authority_info.is_signer
+        || programdata[12] != 1
+        || programdata[13..45] != authority_info.key.to_bytes()
+    {
+        return Err(RegistryError::InvalidUpgradeAuthority.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! State of a program's registry account
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::namespace_hash;
+
+/// The interfaces registered for a program, stored at
+/// `find_registry_address(program_id)`
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct InterfaceRegistry {
+    /// The program the registry belongs to
+    pub program_id: Pubkey,
+    /// The registered interfaces, sorted by namespace hash
+    pub entries: Vec<InterfaceEntry>,
+}
+
+/// A registered interface and the versions of it the program
+/// implements
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceEntry {
+    /// The hash of the interface's namespace
+    pub namespace_hash: [u8; 32],
+    /// The registered versions, in ascending order
+    pub versions: Vec<u8>,
+}
+
+impl InterfaceRegistry {
+    /// Decodes a registry account's data
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        Self::deserialize(&mut &data[..])
+    }
+    /// Looks up an interface's entry by its namespace
+    pub fn entry(&self, namespace: &str) -> Option<&InterfaceEntry> {
+        let namespace_hash = namespace_hash(namespace);
+        self.entries
+            .iter()
+            .find(|entry| entry.namespace_hash == namespace_hash)
+    }
+    /// Whether the program registered the given interface version
+    pub fn supports(&self, namespace: &str, version: u8) -> bool {
+        self.entry(namespace)
+            .map(|entry| entry.versions.contains(&version))
+            .unwrap_or(false)
+    }
+    /// Registers an interface version, returning `false` if it was
+    /// already registered
+    pub fn register(&mut self, namespace_hash: [u8; 32], version: u8) -> bool {
+        let index = match self
+            .entries
+            .binary_search_by(|entry| entry.namespace_hash.cmp(&namespace_hash))
+        {
+            Ok(index) => index,
+            Err(index) => {
+                self.entries.insert(
+                    index,
+                    InterfaceEntry {
+                        namespace_hash,
+                        versions: vec![],
+                    },
+                );
+                index
+            }
+        };
+        let versions = &mut self.entries[index].versions;
+        match versions.binary_search(&version) {
+            Ok(_) => false,
+            Err(position) => {
+                versions.insert(position, version);
+                true
+            }
+        }
+    }
+    /// Unregisters an interface version, dropping the interface's
+    /// entry once no version is left, and returning `false` if it
+    /// wasn't registered
+    pub fn unregister(&mut self, namespace_hash: [u8; 32], version: u8) -> bool {
+        let index = match self
+            .entries
+            .binary_search_by(|entry| entry.namespace_hash.cmp(&namespace_hash))
+        {
+            Ok(index) => index,
+            Err(_) => return false,
+        };
+        let versions = &mut self.entries[index].versions;
+        match versions.binary_search(&version) {
+            Ok(position) => {
+                versions.remove(position);
+                if versions.is_empty() {
+                    self.entries.remove(index);
+                }
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
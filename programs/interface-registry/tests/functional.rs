@@ -0,0 +1,206 @@
+use solana_program_test::{processor, tokio, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use spl_interface_registry::{
+    error::RegistryError,
+    find_registry_address, id,
+    instruction::{register_interface, unregister_interface},
+    processor::process,
+    state::InterfaceRegistry,
+};
+
+/// Sets up a bank with an upgradeable program whose upgrade authority
+/// is `authority`
+async fn setup(authority: &Pubkey) -> (ProgramTestContext, Pubkey) {
+    let mut program_test = ProgramTest::new("spl_interface_registry", id(), processor!(process));
+    let program_id = Pubkey::new_unique();
+    let (programdata_address, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    program_test.add_account(
+        program_id,
+        Account {
+            lamports: 1_000_000_000,
+            data: bincode::serialize(&UpgradeableLoaderState::Program {
+                programdata_address,
+            })
+            .unwrap(),
+            owner: bpf_loader_upgradeable::id(),
+            executable: true,
+            rent_epoch: 0,
+        },
+    );
+    program_test.add_account(
+        programdata_address,
+        Account {
+            lamports: 1_000_000_000,
+            data: bincode::serialize(&UpgradeableLoaderState::ProgramData {
+                slot: 0,
+                upgrade_authority_address: Some(*authority),
+            })
+            .unwrap(),
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    (program_test.start_with_context().await, program_id)
+}
+
+async fn send(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    authority: &Keypair,
+) -> Result<(), TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+}
+
+async fn registry(context: &mut ProgramTestContext, program_id: &Pubkey) -> InterfaceRegistry {
+    let account = context
+        .banks_client
+        .get_account(find_registry_address(program_id).0)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.owner, id());
+    InterfaceRegistry::unpack(&account.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_register_interfaces() {
+    let authority = Keypair::new();
+    let (mut context, program_id) = setup(&authority.pubkey()).await;
+    let payer = context.payer.pubkey();
+    for (namespace, version) in [
+        ("srfc20_token", 1),
+        ("srfc20_token", 2),
+        ("srfc21_token", 1),
+    ] {
+        let instruction =
+            register_interface(&program_id, &authority.pubkey(), &payer, namespace, version);
+        send(&mut context, instruction, &authority).await.unwrap();
+    }
+    let registry = registry(&mut context, &program_id).await;
+    assert_eq!(registry.program_id, program_id);
+    assert_eq!(registry.entries.len(), 2);
+    assert_eq!(registry.entry("srfc20_token").unwrap().versions, vec![1, 2]);
+    assert!(registry.supports("srfc21_token", 1));
+    assert!(!registry.supports("srfc21_token", 2));
+    assert!(!registry.supports("srfc22_associated_token", 1));
+
+    // Registering the same version twice fails
+    let instruction =
+        register_interface(&program_id, &authority.pubkey(), &payer, "srfc21_token", 1);
+    assert_eq!(
+        send(&mut context, instruction, &authority).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(RegistryError::AlreadyRegistered as u32)
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_register_with_funded_registry_address() {
+    let authority = Keypair::new();
+    let (mut context, program_id) = setup(&authority.pubkey()).await;
+    let payer = context.payer.pubkey();
+    // Anyone can send lamports to the registry address before it's created
+    let registry_address = find_registry_address(&program_id).0;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer,
+            &registry_address,
+            rent.minimum_balance(0),
+        )],
+        Some(&payer),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let instruction =
+        register_interface(&program_id, &authority.pubkey(), &payer, "srfc20_token", 1);
+    send(&mut context, instruction, &authority).await.unwrap();
+    let registry = registry(&mut context, &program_id).await;
+    assert!(registry.supports("srfc20_token", 1));
+}
+
+#[tokio::test]
+async fn test_unregister_interface() {
+    let authority = Keypair::new();
+    let (mut context, program_id) = setup(&authority.pubkey()).await;
+    let payer = context.payer.pubkey();
+    for version in [1, 2] {
+        let instruction = register_interface(
+            &program_id,
+            &authority.pubkey(),
+            &payer,
+            "srfc20_token",
+            version,
+        );
+        send(&mut context, instruction, &authority).await.unwrap();
+    }
+    let instruction =
+        unregister_interface(&program_id, &authority.pubkey(), &payer, "srfc20_token", 1);
+    send(&mut context, instruction, &authority).await.unwrap();
+    let registry = registry(&mut context, &program_id).await;
+    assert!(!registry.supports("srfc20_token", 1));
+    assert!(registry.supports("srfc20_token", 2));
+
+    let instruction =
+        unregister_interface(&program_id, &authority.pubkey(), &payer, "srfc20_token", 1);
+    assert_eq!(
+        send(&mut context, instruction, &authority).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(RegistryError::NotRegistered as u32)
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_only_upgrade_authority_registers() {
+    let authority = Keypair::new();
+    let (mut context, program_id) = setup(&authority.pubkey()).await;
+    let payer = context.payer.pubkey();
+    let impostor = Keypair::new();
+    let instruction =
+        register_interface(&program_id, &impostor.pubkey(), &payer, "srfc20_token", 1);
+    assert_eq!(
+        send(&mut context, instruction, &impostor).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(RegistryError::InvalidUpgradeAuthority as u32)
+        ))
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .get_account(find_registry_address(&program_id).0)
+            .await
+            .unwrap(),
+        None
+    );
+}
@@ -35,10 +35,28 @@ pub enum SampleProgramInstruction {
     /// instruction and will have discriminator `hash(token:thaw)[..8]`
     #[interface(srfc22_associated_token::thaw)]
     Thaw,
+    /// This instruction implements the `example_token_extensions` interface's
+    /// `balance_of` view, which returns a `u64` through
+    /// `SampleProgramInstruction::set_balance_of_return_data`
+    #[interface(example_token_extensions::balance_of)]
+    BalanceOf { owner: Pubkey },
     Custom {
         custom_arg_1: Pubkey,
         custom_arg_2: u32,
     },
 }
 
+/// Programs can also map their errors onto an interface's error codes,
+/// so that callers recognize them whichever program returned them.
+/// Variants without an annotation keep their own discriminants
+#[derive(Clone, Copy, Debug, PartialEq, SplInterfaceError)]
+pub enum SampleProgramError {
+    #[interface_error(example_token_extensions::insufficient_funds)]
+    InsufficientFunds,
+    #[interface_error(example_token_extensions::account_frozen)]
+    AccountFrozen,
+    Overflow,
+}
+
+#[cfg(not(feature = "no-entrypoint"))]
 entrypoint!(process);
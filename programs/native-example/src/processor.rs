@@ -25,6 +25,10 @@ fn process_thaw() -> ProgramResult {
     Ok(())
 }
 
+fn process_balance_of(owner: Pubkey) -> ProgramResult {
+    SampleProgramInstruction::set_balance_of_return_data(&0)
+}
+
 pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     match SampleProgramInstruction::unpack(data) {
         Ok(ix) => match ix {
@@ -47,6 +51,7 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> P
                 custom_arg_2,
             } => process_freeze(),
             SampleProgramInstruction::Thaw { custom_arg_1 } => process_thaw(),
+            SampleProgramInstruction::BalanceOf { owner } => process_balance_of(owner),
         },
         Err(_) => Err(ProgramError::InvalidInstructionData),
     }
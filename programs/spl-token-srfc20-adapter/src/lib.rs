@@ -0,0 +1,75 @@
+// This file was generated by spl-interface-instructions-codegen.
+// Do not edit it by hand.
+//! Adapter exposing `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA` through the `srfc20_token` interface
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The legacy program interface instructions are forwarded to
+pub const LEGACY_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+/// Routes an interface instruction to its legacy counterpart
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    if data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (discriminator, args) = data.split_at(8);
+    match discriminator {
+        // srfc20_token::mint_to
+        [72, 188, 206, 219, 176, 166, 155, 233] => forward(accounts, 3, &[7], args, &[0, 1, 2]),
+        // srfc20_token::transfer
+        [62, 41, 116, 21, 138, 86, 202, 221] => forward(accounts, 4, &[3], args, &[2, 1, 3]),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Forwards an instruction to the legacy program, which follows the
+/// `interface_accounts` interface accounts, reordered by `order`
+fn forward(
+    accounts: &[AccountInfo],
+    interface_accounts: usize,
+    legacy_data: &[u8],
+    args: &[u8],
+    order: &[usize],
+) -> ProgramResult {
+    let legacy_program = accounts
+        .get(interface_accounts)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if legacy_program.key != &LEGACY_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut account_infos: Vec<AccountInfo> = order.iter().map(|&i| accounts[i].clone()).collect();
+    let metas = account_infos
+        .iter()
+        .map(|a| AccountMeta {
+            pubkey: *a.key,
+            is_signer: a.is_signer,
+            is_writable: a.is_writable,
+        })
+        .collect();
+    let mut data = legacy_data.to_vec();
+    data.extend_from_slice(args);
+    account_infos.push(legacy_program.clone());
+    invoke(
+        &Instruction {
+            program_id: LEGACY_PROGRAM_ID,
+            accounts: metas,
+            data,
+        },
+        &account_infos,
+    )
+}
@@ -0,0 +1,166 @@
+//! Runs the generated adapter against the SPL Token program bundled
+//! with `solana-program-test`
+
+use solana_program_test::{processor, tokio, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use spl_token_srfc20_adapter::{process_instruction, LEGACY_PROGRAM_ID};
+
+/// Size of an SPL Token mint
+const MINT_LEN: usize = 82;
+/// Size of an SPL Token account
+const ACCOUNT_LEN: usize = 165;
+
+/// Builds an interface instruction for the adapter, with the interface
+/// accounts followed by the legacy program
+fn interface_ix(
+    adapter_id: &Pubkey,
+    instruction_namespace: &str,
+    accounts: Vec<AccountMeta>,
+    amount: u64,
+) -> Instruction {
+    let preimage = format!("srfc20_token:{}", instruction_namespace);
+    let mut data = hash(preimage.as_bytes()).to_bytes()[..8].to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    let mut accounts = accounts;
+    accounts.push(AccountMeta::new_readonly(LEGACY_PROGRAM_ID, false));
+    Instruction {
+        program_id: *adapter_id,
+        accounts,
+        data,
+    }
+}
+
+async fn send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) {
+    let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+/// Reads the balance of an SPL Token account
+async fn amount(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    let data = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+#[tokio::test]
+async fn test_transfer_through_adapter() {
+    let adapter_id = Pubkey::new_unique();
+    let mut context = ProgramTest::new(
+        "spl_token_srfc20_adapter",
+        adapter_id,
+        processor!(process_instruction),
+    )
+    .start_with_context()
+    .await;
+    let payer = context.payer.pubkey();
+    let mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let alice_account = Keypair::new();
+    let bob_account = Keypair::new();
+
+    // Set up the mint and accounts with SPL Token's own instructions
+    // (`InitializeMint2` and `InitializeAccount3`)
+    let mut initialize_mint = vec![20, 0];
+    initialize_mint.extend_from_slice(mint_authority.pubkey().as_ref());
+    initialize_mint.push(0);
+    let mut instructions = vec![
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            Rent::default().minimum_balance(MINT_LEN),
+            MINT_LEN as u64,
+            &LEGACY_PROGRAM_ID,
+        ),
+        Instruction {
+            program_id: LEGACY_PROGRAM_ID,
+            accounts: vec![AccountMeta::new(mint.pubkey(), false)],
+            data: initialize_mint,
+        },
+    ];
+    for (account, owner) in [(&alice_account, &alice), (&bob_account, &bob)] {
+        let mut initialize_account = vec![18];
+        initialize_account.extend_from_slice(owner.pubkey().as_ref());
+        instructions.push(system_instruction::create_account(
+            &payer,
+            &account.pubkey(),
+            Rent::default().minimum_balance(ACCOUNT_LEN),
+            ACCOUNT_LEN as u64,
+            &LEGACY_PROGRAM_ID,
+        ));
+        instructions.push(Instruction {
+            program_id: LEGACY_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(account.pubkey(), false),
+                AccountMeta::new_readonly(mint.pubkey(), false),
+            ],
+            data: initialize_account,
+        });
+    }
+    send(
+        &mut context,
+        &instructions,
+        &[&mint, &alice_account, &bob_account],
+    )
+    .await;
+
+    // `mint_to` and `transfer` go through the adapter, which CPIs into
+    // SPL Token with its own tags and account order
+    let mint_to = interface_ix(
+        &adapter_id,
+        "mint_to",
+        vec![
+            AccountMeta::new(mint.pubkey(), false),
+            AccountMeta::new(alice_account.pubkey(), false),
+            AccountMeta::new_readonly(mint_authority.pubkey(), true),
+        ],
+        100,
+    );
+    send(&mut context, &[mint_to], &[&mint_authority]).await;
+    assert_eq!(amount(&mut context, &alice_account.pubkey()).await, 100);
+
+    let transfer = interface_ix(
+        &adapter_id,
+        "transfer",
+        vec![
+            AccountMeta::new_readonly(mint.pubkey(), false),
+            AccountMeta::new(bob_account.pubkey(), false),
+            AccountMeta::new(alice_account.pubkey(), false),
+            AccountMeta::new_readonly(alice.pubkey(), true),
+        ],
+        30,
+    );
+    send(&mut context, &[transfer], &[&alice]).await;
+    assert_eq!(amount(&mut context, &alice_account.pubkey()).await, 70);
+    assert_eq!(amount(&mut context, &bob_account.pubkey()).await, 30);
+}
@@ -0,0 +1,16 @@
+//! Errors returned by the token program
+//!
+//! sRFC 20 doesn't define interface errors, so the codes are the
+//! program's own, in declaration order
+
+use spl_interface_instructions::*;
+
+/// Errors returned by the token program
+#[derive(Clone, Copy, Debug, PartialEq, Eq, SplInterfaceError)]
+pub enum TokenError {
+    InsufficientFunds,
+    AccountFrozen,
+    OwnerMismatch,
+    MintMismatch,
+    Overflow,
+}
@@ -0,0 +1,126 @@
+//! Instructions of the token program, along with their builders
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use spl_interface_instructions::*;
+
+/// Instructions of the token program
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, SplInterfaceInstruction)]
+pub enum TokenInstruction {
+    /// Initializes a mint created by the client with `Mint::LEN` bytes
+    ///
+    /// 0. `[writable]` The mint
+    InitializeMint {
+        mint_authority: Pubkey,
+        freeze_authority: Option<Pubkey>,
+    },
+    /// Initializes a token account created by the client with
+    /// `TokenAccount::LEN` bytes
+    ///
+    /// 0. `[writable]` The token account
+    /// 1. `[]` The mint
+    /// 2. `[]` The account's owner
+    InitializeAccount,
+    /// Mints tokens to a token account
+    ///
+    /// 0. `[writable]` The mint
+    /// 1. `[writable]` The destination token account
+    /// 2. `[signer]` The mint authority
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u64 },
+    /// Transfers tokens between token accounts of the same mint
+    ///
+    /// 0. `[]` The mint
+    /// 1. `[writable]` The recipient token account
+    /// 2. `[writable]` The source token account
+    /// 3. `[signer]` The source account's owner
+    #[interface(srfc20_token::transfer)]
+    Transfer { amount: u64 },
+}
+
+/// Packs an instruction's data
+pub fn pack_data<T: InterfaceInstructionPack>(instruction: &T) -> Vec<u8> {
+    let mut data = vec![];
+    instruction
+        .pack(&mut data)
+        .expect("Instruction data is always serializable");
+    data
+}
+
+/// Creates an `InitializeMint` instruction
+pub fn initialize_mint(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![AccountMeta::new(*mint, false)],
+        data: pack_data(&TokenInstruction::InitializeMint {
+            mint_authority: *mint_authority,
+            freeze_authority: freeze_authority.copied(),
+        }),
+    }
+}
+
+/// Creates an `InitializeAccount` instruction
+pub fn initialize_account(
+    program_id: &Pubkey,
+    account: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*owner, false),
+        ],
+        data: pack_data(&TokenInstruction::InitializeAccount),
+    }
+}
+
+/// Creates a `MintTo` instruction
+pub fn mint_to(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: pack_data(&TokenInstruction::MintTo { amount }),
+    }
+}
+
+/// Creates a `Transfer` instruction
+pub fn transfer(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    recipient: &Pubkey,
+    from: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*recipient, false),
+            AccountMeta::new(*from, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: pack_data(&TokenInstruction::Transfer { amount }),
+    }
+}
@@ -0,0 +1,16 @@
+//! Reference implementation of the sRFC 20 Token Interface
+//!
+//! A minimal token where each mint has a single mint authority and
+//! token accounts are created by the client and then initialized.
+//! Its state and processors are reused by the other reference
+//! programs, which all require sRFC 20
+
+pub mod error;
+pub mod instruction;
+pub mod processor;
+pub mod state;
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
+
+solana_program::declare_id!("EVWR1V6khA37yBFTkWNZ3JGRZWkChzPgHd2aDS7iJtby");
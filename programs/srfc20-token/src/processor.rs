@@ -0,0 +1,166 @@
+//! Processor for the token program
+//!
+//! Each instruction's processor is public, so that programs extending
+//! the token with other interfaces can dispatch to it
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_interface_instructions::*;
+
+use crate::{
+    error::TokenError,
+    instruction::TokenInstruction,
+    state::{Mint, TokenAccount, TransferEvent},
+};
+
+/// Processes a `TokenInstruction`
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match TokenInstruction::unpack(data)? {
+        TokenInstruction::InitializeMint {
+            mint_authority,
+            freeze_authority,
+        } => process_initialize_mint(program_id, accounts, mint_authority, freeze_authority),
+        TokenInstruction::InitializeAccount => process_initialize_account(program_id, accounts),
+        TokenInstruction::MintTo { amount } => process_mint_to(program_id, accounts, amount),
+        TokenInstruction::Transfer { amount } => process_transfer(program_id, accounts, amount),
+    }
+}
+
+/// Processes an `InitializeMint` instruction
+pub fn process_initialize_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint_authority: Pubkey,
+    freeze_authority: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    if mint_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if mint_info.data_len() < Mint::LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    if Mint::load(mint_info, program_id).is_ok() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    Mint {
+        is_initialized: true,
+        mint_authority,
+        freeze_authority,
+        supply: 0,
+    }
+    .save(mint_info)
+}
+
+/// Processes an `InitializeAccount` instruction
+pub fn process_initialize_account(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    if account_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if account_info.data_len() < TokenAccount::LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    if TokenAccount::load(account_info, program_id).is_ok() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    Mint::load(mint_info, program_id)?;
+    TokenAccount {
+        mint: *mint_info.key,
+        owner: *owner_info.key,
+        amount: 0,
+        state: TokenAccount::INITIALIZED,
+    }
+    .save(account_info)
+}
+
+/// Processes a `MintTo` instruction (sRFC 20 `mint_to`)
+pub fn process_mint_to(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let mut mint = Mint::load(mint_info, program_id)?;
+    let mut destination = TokenAccount::load(destination_info, program_id)?;
+    if mint.mint_authority != *authority_info.key {
+        return Err(TokenError::OwnerMismatch.into());
+    }
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if destination.mint != *mint_info.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+    if destination.is_frozen() {
+        return Err(TokenError::AccountFrozen.into());
+    }
+    mint.supply = mint
+        .supply
+        .checked_add(amount)
+        .ok_or(TokenError::Overflow)?;
+    destination.amount = destination
+        .amount
+        .checked_add(amount)
+        .ok_or(TokenError::Overflow)?;
+    mint.save(mint_info)?;
+    destination.save(destination_info)
+}
+
+/// Processes a `Transfer` instruction (sRFC 20 `transfer`), emitting
+/// the sRFC 20 `transfer` event
+pub fn process_transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let recipient_info = next_account_info(account_info_iter)?;
+    let from_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let mut from = TokenAccount::load(from_info, program_id)?;
+    let mut recipient = TokenAccount::load(recipient_info, program_id)?;
+    if from.owner != *authority_info.key {
+        return Err(TokenError::OwnerMismatch.into());
+    }
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if from.mint != *mint_info.key || recipient.mint != *mint_info.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+    if from.is_frozen() || recipient.is_frozen() {
+        return Err(TokenError::AccountFrozen.into());
+    }
+    if from.amount < amount {
+        return Err(TokenError::InsufficientFunds.into());
+    }
+    // A transfer to the source account leaves balances unchanged
+    if from_info.key != recipient_info.key {
+        from.amount -= amount;
+        recipient.amount = recipient
+            .amount
+            .checked_add(amount)
+            .ok_or(TokenError::Overflow)?;
+        from.save(from_info)?;
+        recipient.save(recipient_info)?;
+    }
+    TransferEvent {
+        from: *from_info.key,
+        to: *recipient_info.key,
+        amount,
+    }
+    .emit()
+}
@@ -0,0 +1,114 @@
+//! State of the token program's accounts
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+use spl_interface_instructions::*;
+
+/// A token mint
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mint {
+    /// Whether the mint has been initialized
+    pub is_initialized: bool,
+    /// The authority allowed to mint new tokens
+    pub mint_authority: Pubkey,
+    /// The authority allowed to freeze token accounts, if any
+    /// (sRFC 22)
+    pub freeze_authority: Option<Pubkey>,
+    /// The total supply of tokens
+    pub supply: u64,
+}
+impl Mint {
+    /// The size of a mint account, including its discriminator, with
+    /// room for a freeze authority
+    pub const LEN: usize = 8 + 1 + 32 + 33 + 8;
+    /// The discriminator prefixing a mint account, so that it can't be
+    /// mistaken for another account of the program
+    /// (`hash("account:Mint")[..8]`)
+    pub const DISCRIMINATOR: [u8; 8] = [80, 188, 245, 20, 95, 138, 57, 156];
+
+    /// Deserializes a mint from its account data, checking its
+    /// discriminator
+    pub fn try_from_slice(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < 8 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (discriminator, mut data) = data.split_at(8);
+        let mint = Self::deserialize(&mut data).map_err(|_| ProgramError::InvalidAccountData)?;
+        if !mint.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if discriminator != Self::DISCRIMINATOR {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(mint)
+    }
+    /// Loads an initialized mint owned by the program
+    pub fn load(info: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Self::try_from_slice(&info.try_borrow_data()?)
+    }
+    /// Stores the mint in its account, after its discriminator
+    pub fn save(&self, info: &AccountInfo) -> Result<(), ProgramError> {
+        let mut data = info.try_borrow_mut_data()?;
+        if data.len() < 8 {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        let (discriminator, mut data) = data.split_at_mut(8);
+        discriminator.copy_from_slice(&Self::DISCRIMINATOR);
+        self.serialize(&mut data)
+            .map_err(|e| ProgramError::BorshIoError(e.to_string()))
+    }
+}
+
+/// A token account, laid out as the sRFC 20 `token_account`
+#[derive(Clone, Debug, Default, PartialEq, Eq, SplInterfaceAccount)]
+#[interface_account(srfc20_token::token_account)]
+pub struct TokenAccount {
+    /// The mint of the account's tokens
+    pub mint: Pubkey,
+    /// The owner of the account
+    pub owner: Pubkey,
+    /// The amount of tokens held
+    pub amount: u64,
+    /// The account's state, either `INITIALIZED` or `FROZEN` (sRFC 22)
+    pub state: u8,
+}
+impl TokenAccount {
+    /// The size of a token account, including its discriminator
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+    /// State of an account that can send and receive tokens
+    pub const INITIALIZED: u8 = 0;
+    /// State of an account frozen by the mint's freeze authority
+    pub const FROZEN: u8 = 1;
+
+    /// Whether the account is frozen
+    pub fn is_frozen(&self) -> bool {
+        self.state == Self::FROZEN
+    }
+
+    /// Loads an initialized token account owned by the program
+    pub fn load(info: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Self::try_from_slice(&info.try_borrow_data()?)
+    }
+    /// Stores the token account in its account
+    pub fn save(&self, info: &AccountInfo) -> Result<(), ProgramError> {
+        self.pack(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+}
+
+/// The sRFC 20 `transfer` event
+#[derive(Clone, Debug, PartialEq, Eq, SplInterfaceEvent)]
+#[interface_event(srfc20_token::transfer)]
+pub struct TransferEvent {
+    /// The token account tokens were transferred from
+    pub from: Pubkey,
+    /// The token account tokens were transferred to
+    pub to: Pubkey,
+    /// The amount of tokens transferred
+    pub amount: u64,
+}
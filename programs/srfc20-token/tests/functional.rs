@@ -0,0 +1,137 @@
+use solana_program_test::{processor, tokio, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use spl_interface_instructions::events::parse_events;
+use spl_interface_instructions_conformance::{create_account, send};
+use srfc20_token::{
+    error::TokenError,
+    id,
+    instruction::{initialize_account, initialize_mint, mint_to, transfer},
+    processor::process,
+    state::{Mint, TokenAccount, TransferEvent},
+};
+
+/// Creates a mint along with a token account for each owner
+async fn setup(
+    mint_authority: &Keypair,
+    owners: &[Pubkey],
+) -> (ProgramTestContext, Pubkey, Vec<Pubkey>) {
+    let mut context = ProgramTest::new("srfc20_token", id(), processor!(process))
+        .start_with_context()
+        .await;
+    let mint = Keypair::new();
+    let mut instructions = vec![
+        create_account(&context.payer.pubkey(), &mint.pubkey(), Mint::LEN, &id()),
+        initialize_mint(&id(), &mint.pubkey(), &mint_authority.pubkey(), None),
+    ];
+    let accounts: Vec<Keypair> = owners.iter().map(|_| Keypair::new()).collect();
+    for (account, owner) in accounts.iter().zip(owners) {
+        instructions.push(create_account(
+            &context.payer.pubkey(),
+            &account.pubkey(),
+            TokenAccount::LEN,
+            &id(),
+        ));
+        instructions.push(initialize_account(
+            &id(),
+            &account.pubkey(),
+            &mint.pubkey(),
+            owner,
+        ));
+    }
+    let mut signers = vec![&mint];
+    signers.extend(accounts.iter());
+    send(&mut context, &instructions, &signers).await.unwrap();
+    let accounts = accounts.iter().map(|account| account.pubkey()).collect();
+    (context, mint.pubkey(), accounts)
+}
+
+async fn token_account(context: &mut ProgramTestContext, address: &Pubkey) -> TokenAccount {
+    let account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .unwrap();
+    TokenAccount::try_from_slice(&account.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_mint_and_transfer() {
+    let mint_authority = Keypair::new();
+    let alice = Keypair::new();
+    let bob = Pubkey::new_unique();
+    let (mut context, mint, accounts) = setup(&mint_authority, &[alice.pubkey(), bob]).await;
+    let ix = mint_to(&id(), &mint, &accounts[0], &mint_authority.pubkey(), 100);
+    send(&mut context, &[ix], &[&mint_authority]).await.unwrap();
+
+    let ix = transfer(
+        &id(),
+        &mint,
+        &accounts[1],
+        &accounts[0],
+        &alice.pubkey(),
+        40,
+    );
+    let logs = send(&mut context, &[ix], &[&alice]).await.unwrap();
+    assert_eq!(token_account(&mut context, &accounts[0]).await.amount, 60);
+    assert_eq!(token_account(&mut context, &accounts[1]).await.amount, 40);
+    assert_eq!(
+        parse_events::<TransferEvent>(&logs, Some(&id())),
+        vec![TransferEvent {
+            from: accounts[0],
+            to: accounts[1],
+            amount: 40,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_errors() {
+    let mint_authority = Keypair::new();
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let (mut context, mint, accounts) =
+        setup(&mint_authority, &[alice.pubkey(), bob.pubkey()]).await;
+    let ix = mint_to(&id(), &mint, &accounts[0], &mint_authority.pubkey(), 10);
+    send(&mut context, &[ix], &[&mint_authority]).await.unwrap();
+
+    let ix = transfer(
+        &id(),
+        &mint,
+        &accounts[1],
+        &accounts[0],
+        &alice.pubkey(),
+        11,
+    );
+    let error = send(&mut context, &[ix], &[&alice]).await.unwrap_err();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InsufficientFunds.error_code())
+        )
+    );
+
+    let ix = transfer(&id(), &mint, &accounts[1], &accounts[0], &bob.pubkey(), 1);
+    assert_eq!(
+        send(&mut context, &[ix], &[&bob]).await.unwrap_err(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::OwnerMismatch.error_code())
+        )
+    );
+
+    let ix = mint_to(&id(), &mint, &accounts[0], &alice.pubkey(), 1);
+    assert_eq!(
+        send(&mut context, &[ix], &[&alice]).await.unwrap_err(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::OwnerMismatch.error_code())
+        )
+    );
+}
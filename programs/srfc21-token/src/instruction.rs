@@ -0,0 +1,57 @@
+//! Instructions of the burnable token program, along with their
+//! builders
+//!
+//! The sRFC 20 instructions share their builders with the sRFC 20
+//! reference token, since they're packed identically
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use spl_interface_instructions::*;
+use srfc20_token::instruction::pack_data;
+
+/// Instructions of the burnable token program
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, SplInterfaceInstruction)]
+pub enum BurnableTokenInstruction {
+    /// See `TokenInstruction::InitializeMint`
+    InitializeMint {
+        mint_authority: Pubkey,
+        freeze_authority: Option<Pubkey>,
+    },
+    /// See `TokenInstruction::InitializeAccount`
+    InitializeAccount,
+    /// See `TokenInstruction::MintTo`
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u64 },
+    /// See `TokenInstruction::Transfer`
+    #[interface(srfc20_token::transfer)]
+    Transfer { amount: u64 },
+    /// Burns tokens from a token account
+    ///
+    /// 0. `[writable]` The mint
+    /// 1. `[writable]` The source token account
+    /// 2. `[signer]` The source account's owner
+    #[interface(srfc21_token::burn)]
+    Burn { amount: u64 },
+}
+
+/// Creates a `Burn` instruction
+pub fn burn(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    from: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*from, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: pack_data(&BurnableTokenInstruction::Burn { amount }),
+    }
+}
@@ -0,0 +1,12 @@
+//! Reference implementation of the sRFC 21 Token Interface
+//!
+//! A burnable token: the sRFC 20 reference token, reusing its state
+//! and processors, with `burn` on top
+
+pub mod instruction;
+pub mod processor;
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
+
+solana_program::declare_id!("9rV2ya23hiHKephWZEGNr6hbtS7RkewYPwbkcxkKdMKe");
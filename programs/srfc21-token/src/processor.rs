@@ -0,0 +1,70 @@
+//! Processor for the burnable token program
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_interface_instructions::*;
+use srfc20_token::{
+    error::TokenError,
+    processor::{
+        process_initialize_account, process_initialize_mint, process_mint_to, process_transfer,
+    },
+    state::{Mint, TokenAccount},
+};
+
+use crate::instruction::BurnableTokenInstruction;
+
+/// Processes a `BurnableTokenInstruction`
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match BurnableTokenInstruction::unpack(data)? {
+        BurnableTokenInstruction::InitializeMint {
+            mint_authority,
+            freeze_authority,
+        } => process_initialize_mint(program_id, accounts, mint_authority, freeze_authority),
+        BurnableTokenInstruction::InitializeAccount => {
+            process_initialize_account(program_id, accounts)
+        }
+        BurnableTokenInstruction::MintTo { amount } => {
+            process_mint_to(program_id, accounts, amount)
+        }
+        BurnableTokenInstruction::Transfer { amount } => {
+            process_transfer(program_id, accounts, amount)
+        }
+        BurnableTokenInstruction::Burn { amount } => process_burn(program_id, accounts, amount),
+    }
+}
+
+/// Processes a `Burn` instruction (sRFC 21 `burn`)
+pub fn process_burn(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let from_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let mut mint = Mint::load(mint_info, program_id)?;
+    let mut from = TokenAccount::load(from_info, program_id)?;
+    if from.owner != *authority_info.key {
+        return Err(TokenError::OwnerMismatch.into());
+    }
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if from.mint != *mint_info.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+    if from.is_frozen() {
+        return Err(TokenError::AccountFrozen.into());
+    }
+    if from.amount < amount {
+        return Err(TokenError::InsufficientFunds.into());
+    }
+    from.amount -= amount;
+    mint.supply = mint
+        .supply
+        .checked_sub(amount)
+        .ok_or(TokenError::Overflow)?;
+    mint.save(mint_info)?;
+    from.save(from_info)
+}
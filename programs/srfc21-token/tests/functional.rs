@@ -0,0 +1,90 @@
+use solana_program_test::{processor, tokio, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use spl_interface_instructions_conformance::{create_account, send};
+use srfc20_token::{
+    error::TokenError,
+    instruction::{initialize_account, initialize_mint, mint_to},
+    state::{Mint, TokenAccount},
+};
+use srfc21_token::{id, instruction::burn, processor::process};
+
+async fn account_data(context: &mut ProgramTestContext, address: &Pubkey) -> Vec<u8> {
+    context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .unwrap()
+        .data
+}
+
+#[tokio::test]
+async fn test_burn() {
+    let mut context = ProgramTest::new("srfc21_token", id(), processor!(process))
+        .start_with_context()
+        .await;
+    let mint_authority = Keypair::new();
+    let owner = Keypair::new();
+    let mint = Keypair::new();
+    let account = Keypair::new();
+    let instructions = [
+        create_account(&context.payer.pubkey(), &mint.pubkey(), Mint::LEN, &id()),
+        initialize_mint(&id(), &mint.pubkey(), &mint_authority.pubkey(), None),
+        create_account(
+            &context.payer.pubkey(),
+            &account.pubkey(),
+            TokenAccount::LEN,
+            &id(),
+        ),
+        initialize_account(&id(), &account.pubkey(), &mint.pubkey(), &owner.pubkey()),
+        mint_to(
+            &id(),
+            &mint.pubkey(),
+            &account.pubkey(),
+            &mint_authority.pubkey(),
+            100,
+        ),
+    ];
+    send(
+        &mut context,
+        &instructions,
+        &[&mint, &account, &mint_authority],
+    )
+    .await
+    .unwrap();
+
+    let ix = burn(
+        &id(),
+        &mint.pubkey(),
+        &account.pubkey(),
+        &owner.pubkey(),
+        30,
+    );
+    send(&mut context, &[ix], &[&owner]).await.unwrap();
+    let token_account =
+        TokenAccount::try_from_slice(&account_data(&mut context, &account.pubkey()).await).unwrap();
+    assert_eq!(token_account.amount, 70);
+    let data = account_data(&mut context, &mint.pubkey()).await;
+    let mint_state = Mint::try_from_slice(&data).unwrap();
+    assert_eq!(mint_state.supply, 70);
+
+    let ix = burn(
+        &id(),
+        &mint.pubkey(),
+        &account.pubkey(),
+        &owner.pubkey(),
+        71,
+    );
+    assert_eq!(
+        send(&mut context, &[ix], &[&owner]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::InsufficientFunds.error_code())
+        ))
+    );
+}
@@ -0,0 +1,95 @@
+//! Instructions of the freezable token program, along with their
+//! builders
+//!
+//! The sRFC 20 instructions share their builders with the sRFC 20
+//! reference token, since they're packed identically
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use spl_interface_instructions::*;
+use srfc20_token::instruction::pack_data;
+
+/// Instructions of the freezable token program
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, SplInterfaceInstruction)]
+pub enum FreezableTokenInstruction {
+    /// See `TokenInstruction::InitializeMint`
+    InitializeMint {
+        mint_authority: Pubkey,
+        freeze_authority: Option<Pubkey>,
+    },
+    /// See `TokenInstruction::InitializeAccount`
+    InitializeAccount,
+    /// See `TokenInstruction::MintTo`
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u64 },
+    /// See `TokenInstruction::Transfer`
+    #[interface(srfc20_token::transfer)]
+    Transfer { amount: u64 },
+    /// Freezes a token account
+    ///
+    /// 0. `[]` The mint
+    /// 1. `[writable]` The token account to freeze
+    /// 2. `[signer]` The mint's freeze authority
+    #[interface(srfc22_associated_token::freeze)]
+    Freeze,
+    /// Thaws a frozen token account
+    ///
+    /// 0. `[]` The mint
+    /// 1. `[writable]` The token account to thaw
+    /// 2. `[signer]` The mint's freeze authority
+    #[interface(srfc22_associated_token::thaw)]
+    Thaw,
+}
+
+/// Creates a `Freeze` instruction
+pub fn freeze(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    target: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    freeze_instruction(
+        program_id,
+        mint,
+        target,
+        authority,
+        FreezableTokenInstruction::Freeze,
+    )
+}
+
+/// Creates a `Thaw` instruction
+pub fn thaw(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    target: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    freeze_instruction(
+        program_id,
+        mint,
+        target,
+        authority,
+        FreezableTokenInstruction::Thaw,
+    )
+}
+
+fn freeze_instruction(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    target: &Pubkey,
+    authority: &Pubkey,
+    instruction: FreezableTokenInstruction,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*target, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: pack_data(&instruction),
+    }
+}
@@ -0,0 +1,12 @@
+//! Reference implementation of the sRFC 22 Associated Token Interface
+//!
+//! The sRFC 20 reference token, reusing its state and processors, where
+//! the mint's freeze authority can freeze and thaw token accounts
+
+pub mod instruction;
+pub mod processor;
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
+
+solana_program::declare_id!("EqrK3HoeoLJfBb6SvBjUEu5Bh9LhSVaYSPVqYpoWjfD4");
@@ -0,0 +1,72 @@
+//! Processor for the freezable token program
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_interface_instructions::*;
+use srfc20_token::{
+    error::TokenError,
+    processor::{
+        process_initialize_account, process_initialize_mint, process_mint_to, process_transfer,
+    },
+    state::{Mint, TokenAccount},
+};
+
+use crate::instruction::FreezableTokenInstruction;
+
+/// Processes a `FreezableTokenInstruction`
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match FreezableTokenInstruction::unpack(data)? {
+        FreezableTokenInstruction::InitializeMint {
+            mint_authority,
+            freeze_authority,
+        } => process_initialize_mint(program_id, accounts, mint_authority, freeze_authority),
+        FreezableTokenInstruction::InitializeAccount => {
+            process_initialize_account(program_id, accounts)
+        }
+        FreezableTokenInstruction::MintTo { amount } => {
+            process_mint_to(program_id, accounts, amount)
+        }
+        FreezableTokenInstruction::Transfer { amount } => {
+            process_transfer(program_id, accounts, amount)
+        }
+        FreezableTokenInstruction::Freeze => {
+            process_set_state(program_id, accounts, TokenAccount::FROZEN)
+        }
+        FreezableTokenInstruction::Thaw => {
+            process_set_state(program_id, accounts, TokenAccount::INITIALIZED)
+        }
+    }
+}
+
+/// Processes a `Freeze` or `Thaw` instruction (sRFC 22 `freeze` and
+/// `thaw`), moving the token account to `state`
+pub fn process_set_state(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    state: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let target_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let mint = Mint::load(mint_info, program_id)?;
+    let mut target = TokenAccount::load(target_info, program_id)?;
+    if mint.freeze_authority != Some(*authority_info.key) {
+        return Err(TokenError::OwnerMismatch.into());
+    }
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if target.mint != *mint_info.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+    if target.state == state {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    target.state = state;
+    target.save(target_info)
+}
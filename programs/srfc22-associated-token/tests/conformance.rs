@@ -0,0 +1,23 @@
+use solana_program_test::{processor, tokio, ProgramTest};
+use spl_interface_instructions_conformance::{assert_conformance, Conformance, ProgramFixture};
+use srfc20_token::{
+    instruction::{initialize_account, initialize_mint},
+    state::{Mint, TokenAccount},
+};
+use srfc22_associated_token::{id, processor::process};
+
+#[tokio::test]
+async fn test_conformance() {
+    let fixture = ProgramFixture::new(
+        id(),
+        Mint::LEN,
+        TokenAccount::LEN,
+        initialize_mint,
+        initialize_account,
+    );
+    let program_test = ProgramTest::new("srfc22_associated_token", id(), processor!(process));
+    let mut conformance = Conformance::start(program_test, id(), &fixture).await;
+    let mut reports = conformance.check_srfc20().await;
+    reports.extend(conformance.check_srfc22().await);
+    assert_conformance(&reports);
+}
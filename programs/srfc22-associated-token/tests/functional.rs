@@ -0,0 +1,97 @@
+use solana_program_test::{processor, tokio, ProgramTest};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use spl_interface_instructions_conformance::{create_account, send};
+use srfc20_token::{
+    error::TokenError,
+    instruction::{initialize_account, initialize_mint, mint_to, transfer},
+    state::{Mint, TokenAccount},
+};
+use srfc22_associated_token::{
+    id,
+    instruction::{freeze, thaw},
+    processor::process,
+};
+
+#[tokio::test]
+async fn test_freeze_and_thaw() {
+    let mut context = ProgramTest::new("srfc22_associated_token", id(), processor!(process))
+        .start_with_context()
+        .await;
+    let authority = Keypair::new();
+    let owner = Keypair::new();
+    let mint = Keypair::new();
+    let (from, to) = (Keypair::new(), Keypair::new());
+    let instructions = [
+        create_account(&context.payer.pubkey(), &mint.pubkey(), Mint::LEN, &id()),
+        initialize_mint(
+            &id(),
+            &mint.pubkey(),
+            &authority.pubkey(),
+            Some(&authority.pubkey()),
+        ),
+        create_account(
+            &context.payer.pubkey(),
+            &from.pubkey(),
+            TokenAccount::LEN,
+            &id(),
+        ),
+        initialize_account(&id(), &from.pubkey(), &mint.pubkey(), &owner.pubkey()),
+        create_account(
+            &context.payer.pubkey(),
+            &to.pubkey(),
+            TokenAccount::LEN,
+            &id(),
+        ),
+        initialize_account(&id(), &to.pubkey(), &mint.pubkey(), &owner.pubkey()),
+        mint_to(
+            &id(),
+            &mint.pubkey(),
+            &from.pubkey(),
+            &authority.pubkey(),
+            10,
+        ),
+    ];
+    send(
+        &mut context,
+        &instructions,
+        &[&mint, &from, &to, &authority],
+    )
+    .await
+    .unwrap();
+
+    let ix = freeze(&id(), &mint.pubkey(), &from.pubkey(), &authority.pubkey());
+    send(&mut context, &[ix], &[&authority]).await.unwrap();
+    let transfer_ix = transfer(
+        &id(),
+        &mint.pubkey(),
+        &to.pubkey(),
+        &from.pubkey(),
+        &owner.pubkey(),
+        1,
+    );
+    assert_eq!(
+        send(&mut context, &[transfer_ix.clone()], &[&owner]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::AccountFrozen.error_code())
+        ))
+    );
+
+    // Only the freeze authority can thaw
+    let ix = thaw(&id(), &mint.pubkey(), &from.pubkey(), &owner.pubkey());
+    assert_eq!(
+        send(&mut context, &[ix], &[&owner]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::OwnerMismatch.error_code())
+        ))
+    );
+    let ix = thaw(&id(), &mint.pubkey(), &from.pubkey(), &authority.pubkey());
+    send(&mut context, &[ix, transfer_ix], &[&authority, &owner])
+        .await
+        .unwrap();
+}
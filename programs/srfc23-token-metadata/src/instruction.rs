@@ -0,0 +1,113 @@
+//! Instructions of the token metadata program, along with their
+//! builders
+//!
+//! The sRFC 20 instructions share their builders with the sRFC 20
+//! reference token, since they're packed identically
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+use spl_interface_instructions::*;
+use srfc20_token::instruction::pack_data;
+
+use crate::find_metadata_address;
+
+/// Instructions of the token metadata program
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, SplInterfaceInstruction)]
+pub enum MetadataTokenInstruction {
+    /// See `TokenInstruction::InitializeMint`
+    InitializeMint {
+        mint_authority: Pubkey,
+        freeze_authority: Option<Pubkey>,
+    },
+    /// See `TokenInstruction::InitializeAccount`
+    InitializeAccount,
+    /// See `TokenInstruction::MintTo`
+    #[interface(srfc20_token::mint_to)]
+    MintTo { amount: u64 },
+    /// See `TokenInstruction::Transfer`
+    #[interface(srfc20_token::transfer)]
+    Transfer { amount: u64 },
+    /// Creates a mint's metadata, with the mint authority as its
+    /// update authority
+    ///
+    /// 0. `[]` The mint
+    /// 1. `[writable]` The metadata account
+    /// 2. `[signer]` The mint authority
+    /// 3. `[writable, signer]` The payer
+    /// 4. `[]` The system program
+    #[interface(srfc23_token_metadata::create_metadata)]
+    CreateMetadata {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    /// Updates a mint's metadata
+    ///
+    /// 0. `[writable]` The metadata account
+    /// 1. `[signer]` The update authority
+    /// 2. `[writable, signer]` The payer covering any change in rent
+    /// 3. `[]` The system program
+    #[interface(srfc23_token_metadata::update_metadata)]
+    UpdateMetadata {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+}
+
+/// Creates a `CreateMetadata` instruction
+pub fn create_metadata(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(find_metadata_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: pack_data(&MetadataTokenInstruction::CreateMetadata {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        }),
+    }
+}
+
+/// Creates an `UpdateMetadata` instruction
+pub fn update_metadata(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(find_metadata_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: pack_data(&MetadataTokenInstruction::UpdateMetadata {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,24 @@
+//! Reference implementation of the sRFC 23 Token Metadata Interface
+//!
+//! The sRFC 20 reference token, reusing its state and processors, with
+//! metadata stored in a PDA per mint
+
+pub mod instruction;
+pub mod processor;
+pub mod state;
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
+
+use solana_program::pubkey::Pubkey;
+
+solana_program::declare_id!("APxva6kMpstMaA3c535JW1YkkJXGrEvMHp9vygrw2z3S");
+
+/// Seed prefix of a mint's metadata account
+pub const METADATA_SEED: &[u8] = b"metadata";
+
+/// Returns the address of a mint's metadata account for the program
+/// `program_id`, along with its bump seed
+pub fn find_metadata_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[METADATA_SEED, mint.as_ref()], program_id)
+}
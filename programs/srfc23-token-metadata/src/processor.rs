@@ -0,0 +1,171 @@
+//! Processor for the token metadata program
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+use spl_interface_instructions::*;
+use srfc20_token::{
+    error::TokenError,
+    processor::{
+        process_initialize_account, process_initialize_mint, process_mint_to, process_transfer,
+    },
+    state::Mint,
+};
+
+use crate::{
+    find_metadata_address, instruction::MetadataTokenInstruction, state::Metadata, METADATA_SEED,
+};
+
+/// Processes a `MetadataTokenInstruction`
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match MetadataTokenInstruction::unpack(data)? {
+        MetadataTokenInstruction::InitializeMint {
+            mint_authority,
+            freeze_authority,
+        } => process_initialize_mint(program_id, accounts, mint_authority, freeze_authority),
+        MetadataTokenInstruction::InitializeAccount => {
+            process_initialize_account(program_id, accounts)
+        }
+        MetadataTokenInstruction::MintTo { amount } => {
+            process_mint_to(program_id, accounts, amount)
+        }
+        MetadataTokenInstruction::Transfer { amount } => {
+            process_transfer(program_id, accounts, amount)
+        }
+        MetadataTokenInstruction::CreateMetadata { name, symbol, uri } => {
+            process_create_metadata(program_id, accounts, name, symbol, uri)
+        }
+        MetadataTokenInstruction::UpdateMetadata { name, symbol, uri } => {
+            process_update_metadata(program_id, accounts, name, symbol, uri)
+        }
+    }
+}
+
+/// Processes a `CreateMetadata` instruction (sRFC 23 `create_metadata`)
+pub fn process_create_metadata(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let mint = Mint::load(mint_info, program_id)?;
+    if mint.mint_authority != *authority_info.key {
+        return Err(TokenError::OwnerMismatch.into());
+    }
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let (metadata_address, bump) = find_metadata_address(program_id, mint_info.key);
+    if metadata_info.key != &metadata_address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !metadata_info.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    let data = Metadata {
+        mint: *mint_info.key,
+        update_authority: *authority_info.key,
+        name,
+        symbol,
+        uri,
+    }
+    .pack()?;
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_info.key,
+            metadata_info.key,
+            Rent::get()?.minimum_balance(data.len()),
+            data.len() as u64,
+            program_id,
+        ),
+        &[
+            payer_info.clone(),
+            metadata_info.clone(),
+            system_program_info.clone(),
+        ],
+        &[&[METADATA_SEED, mint_info.key.as_ref(), &[bump]]],
+    )?;
+    metadata_info.data.borrow_mut().copy_from_slice(&data);
+    Ok(())
+}
+
+/// Processes an `UpdateMetadata` instruction (sRFC 23
+/// `update_metadata`), resizing the metadata account to fit
+pub fn process_update_metadata(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let metadata = load_metadata(program_id, metadata_info)?;
+    if metadata.update_authority != *authority_info.key {
+        return Err(TokenError::OwnerMismatch.into());
+    }
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let data = Metadata {
+        name,
+        symbol,
+        uri,
+        ..metadata
+    }
+    .pack()?;
+    let required_lamports = Rent::get()?.minimum_balance(data.len());
+    let lamports = metadata_info.lamports();
+    if lamports < required_lamports {
+        invoke(
+            &system_instruction::transfer(
+                payer_info.key,
+                metadata_info.key,
+                required_lamports - lamports,
+            ),
+            &[
+                payer_info.clone(),
+                metadata_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    } else {
+        // Refund the rent freed by shorter metadata
+        let refund = lamports - required_lamports;
+        **metadata_info.try_borrow_mut_lamports()? -= refund;
+        **payer_info.try_borrow_mut_lamports()? += refund;
+    }
+    metadata_info.realloc(data.len(), false)?;
+    metadata_info.data.borrow_mut().copy_from_slice(&data);
+    Ok(())
+}
+
+/// Loads a mint's metadata, checking that it's stored at the mint's
+/// metadata address
+fn load_metadata(
+    program_id: &Pubkey,
+    metadata_info: &AccountInfo,
+) -> Result<Metadata, ProgramError> {
+    let metadata = Metadata::load(metadata_info, program_id)?;
+    if metadata_info.key != &find_metadata_address(program_id, &metadata.mint).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(metadata)
+}
@@ -0,0 +1,51 @@
+//! State of a mint's metadata account
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+/// A mint's metadata, stored at `find_metadata_address(program_id, mint)`
+/// after its discriminator
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metadata {
+    /// The mint the metadata describes
+    pub mint: Pubkey,
+    /// The authority allowed to update the metadata
+    pub update_authority: Pubkey,
+    /// The token's name
+    pub name: String,
+    /// The token's symbol
+    pub symbol: String,
+    /// The URI of the token's off-chain metadata
+    pub uri: String,
+}
+impl Metadata {
+    /// The discriminator prefixing a metadata account, so that it can't
+    /// be mistaken for another account of the program
+    /// (`hash("account:Metadata")[..8]`)
+    pub const DISCRIMINATOR: [u8; 8] = [72, 11, 121, 26, 111, 181, 85, 93];
+
+    /// Deserializes metadata from its account data, checking its
+    /// discriminator
+    pub fn try_from_slice(data: &[u8]) -> Result<Self, ProgramError> {
+        match data.strip_prefix(&Self::DISCRIMINATOR[..]) {
+            Some(mut data) => {
+                Self::deserialize(&mut data).map_err(|_| ProgramError::InvalidAccountData)
+            }
+            None => Err(ProgramError::InvalidAccountData),
+        }
+    }
+    /// Serializes the metadata, prefixed with its discriminator
+    pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut data = Self::DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)
+            .map_err(|e| ProgramError::BorshIoError(e.to_string()))?;
+        Ok(data)
+    }
+    /// Loads the metadata stored in an account owned by the program
+    pub fn load(info: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Self::try_from_slice(&info.try_borrow_data()?)
+    }
+}
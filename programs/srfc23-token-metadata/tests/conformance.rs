@@ -0,0 +1,37 @@
+use solana_program_test::{processor, tokio, ProgramTest};
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, system_program};
+use spl_interface_instructions_conformance::{assert_conformance, Conformance, ProgramFixture};
+use srfc20_token::{
+    instruction::{initialize_account, initialize_mint},
+    state::{Mint, TokenAccount},
+};
+use srfc23_token_metadata::{find_metadata_address, id, processor::process};
+
+/// Accounts the metadata instructions expect after the interface's
+fn extra_accounts(instruction_namespace: &str, payer: &Pubkey) -> Vec<AccountMeta> {
+    match instruction_namespace {
+        "create_metadata" | "update_metadata" => vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        _ => vec![],
+    }
+}
+
+#[tokio::test]
+async fn test_conformance() {
+    let fixture = ProgramFixture::new(
+        id(),
+        Mint::LEN,
+        TokenAccount::LEN,
+        initialize_mint,
+        initialize_account,
+    )
+    .with_metadata_address(|program_id, mint| find_metadata_address(program_id, mint).0)
+    .with_extra_accounts(extra_accounts);
+    let program_test = ProgramTest::new("srfc23_token_metadata", id(), processor!(process));
+    let mut conformance = Conformance::start(program_test, id(), &fixture).await;
+    let mut reports = conformance.check_srfc20().await;
+    reports.extend(conformance.check_srfc23().await);
+    assert_conformance(&reports);
+}
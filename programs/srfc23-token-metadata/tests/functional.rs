@@ -0,0 +1,137 @@
+use solana_program_test::{processor, tokio, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use spl_interface_instructions_conformance::{create_account, send};
+use srfc20_token::{error::TokenError, instruction::initialize_mint, state::Mint};
+use srfc23_token_metadata::{
+    find_metadata_address, id,
+    instruction::{create_metadata, update_metadata},
+    processor::process,
+    state::Metadata,
+};
+
+async fn metadata(context: &mut ProgramTestContext, mint: &Keypair) -> (String, String, String) {
+    let address = find_metadata_address(&id(), &mint.pubkey()).0;
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = Metadata::try_from_slice(&account.data).unwrap();
+    (metadata.name, metadata.symbol, metadata.uri)
+}
+
+#[tokio::test]
+async fn test_create_and_update_metadata() {
+    let mut context = ProgramTest::new("srfc23_token_metadata", id(), processor!(process))
+        .start_with_context()
+        .await;
+    let authority = Keypair::new();
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let instructions = [
+        create_account(&payer, &mint.pubkey(), Mint::LEN, &id()),
+        initialize_mint(&id(), &mint.pubkey(), &authority.pubkey(), None),
+        create_metadata(
+            &id(),
+            &mint.pubkey(),
+            &authority.pubkey(),
+            &payer,
+            "Token",
+            "TKN",
+            "https://example.com/token.json",
+        ),
+    ];
+    send(&mut context, &instructions, &[&mint, &authority])
+        .await
+        .unwrap();
+    assert_eq!(
+        metadata(&mut context, &mint).await,
+        (
+            "Token".to_string(),
+            "TKN".to_string(),
+            "https://example.com/token.json".to_string()
+        )
+    );
+
+    let ix = update_metadata(
+        &id(),
+        &mint.pubkey(),
+        &authority.pubkey(),
+        &payer,
+        "Renamed Token",
+        "RTKN",
+        "https://example.com/renamed-token.json",
+    );
+    send(&mut context, &[ix], &[&authority]).await.unwrap();
+    assert_eq!(metadata(&mut context, &mint).await.1, "RTKN");
+
+    let impostor = Keypair::new();
+    let ix = update_metadata(
+        &id(),
+        &mint.pubkey(),
+        &impostor.pubkey(),
+        &payer,
+        "",
+        "",
+        "",
+    );
+    assert_eq!(
+        send(&mut context, &[ix], &[&impostor]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::OwnerMismatch.error_code())
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_update_metadata_checks_address() {
+    let mut program_test = ProgramTest::new("srfc23_token_metadata", id(), processor!(process));
+    // Metadata owned by the program, but not at the mint's metadata address
+    let mint = Keypair::new();
+    let impostor = Keypair::new();
+    let spoofed = Pubkey::new_unique();
+    let data = Metadata {
+        mint: mint.pubkey(),
+        update_authority: impostor.pubkey(),
+        ..Metadata::default()
+    }
+    .pack()
+    .unwrap();
+    program_test.add_account(
+        spoofed,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let mut context = program_test.start_with_context().await;
+    let mut ix = update_metadata(
+        &id(),
+        &mint.pubkey(),
+        &impostor.pubkey(),
+        &context.payer.pubkey(),
+        "Fake",
+        "FAKE",
+        "",
+    );
+    ix.accounts[0].pubkey = spoofed;
+    assert_eq!(
+        send(&mut context, &[ix], &[&impostor]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidSeeds
+        ))
+    );
+}